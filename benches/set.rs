@@ -0,0 +1,59 @@
+//! Measures the cost of a single `set` call with pins that do nothing.
+//!
+//! Run using `cargo bench`. The numbers are only meaningful relative to each other since the
+//! pins don't touch any hardware.
+
+use seven_segment::{Cathode, OutputPin, SevenSegmentPins};
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 10_000_000;
+
+struct NullPin;
+
+impl OutputPin for NullPin {
+    type Error = core::convert::Infallible;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        black_box(true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        black_box(false);
+        Ok(())
+    }
+}
+
+fn bench(name: &str, mut f: impl FnMut(u32)) {
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        f(black_box(i));
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<16} {:>8.2} ns/call",
+        name,
+        elapsed.as_nanos() as f64 / f64::from(ITERATIONS)
+    );
+}
+
+fn main() {
+    let mut display = SevenSegmentPins {
+        a: NullPin,
+        b: NullPin,
+        c: NullPin,
+        d: NullPin,
+        e: NullPin,
+        f: NullPin,
+        g: NullPin,
+    }
+    .with_common::<Cathode>();
+
+    bench("set", |i| display.set((i % 10) as u8).unwrap());
+    bench("set_segments", |i| {
+        display
+            .set_segments(seven_segment::Segments::from_bits(i as u8))
+            .unwrap()
+    });
+}
@@ -0,0 +1,46 @@
+//! Counts from 0 to 9 on a common cathode display connected to Raspberry Pi
+//!
+//! Reference wiring using BCM numbering of the GPIO pins, each segment is connected through a
+//! 330 Ω resistor and the common cathode is connected to ground:
+//!
+//! ```text
+//! segment   a   b   c   d   e   f   g
+//! GPIO      5   6  13  19  26  16  20
+//! ```
+//!
+//! Run it using `cargo run -p raspberry-pi-example`. The pins are
+//! accessed using sysfs so the user needs to have permissions to export them.
+
+use embedded_hal::blocking::delay::DelayMs;
+use linux_embedded_hal::sysfs_gpio::{Direction, Error};
+use linux_embedded_hal::{Delay, Pin};
+use seven_segment::SevenSegmentPins;
+
+/// Exports the pin and configures it as output.
+fn output(number: u64) -> Result<Pin, Error> {
+    let pin = Pin::new(number);
+    pin.0.export()?;
+    pin.0.set_direction(Direction::Low)?;
+    Ok(pin)
+}
+
+fn main() -> Result<(), Error> {
+    let mut display = SevenSegmentPins {
+        a: output(5)?,
+        b: output(6)?,
+        c: output(13)?,
+        d: output(19)?,
+        e: output(26)?,
+        f: output(16)?,
+        g: output(20)?,
+    }
+    .with_common_cathode();
+    let mut delay = Delay;
+
+    loop {
+        for digit in 0..10 {
+            display.set(digit).map_err(|error| error.source)?;
+            delay.delay_ms(500u16);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Counts from 0 to 9 on a common cathode display connected to RP2040, e.g. Raspberry Pi Pico
+//!
+//! Reference wiring, each segment is connected through a 330 Ω resistor and the common cathode
+//! is connected to ground:
+//!
+//! ```text
+//! segment   a      b      c      d      e      f      g
+//! GPIO      2      3      4      5      6      7      8
+//! Pico pin  4      5      6      7      9     10     11
+//! ```
+//!
+//! Here the example is only compiled to check it against the HAL, either on the host or using
+//! `cargo check -p rp2040-example --target thumbv6m-none-eabi`. To flash it copy it into a project
+//! created from [rp2040-project-template](https://github.com/rp-rs/rp2040-project-template),
+//! which provides `memory.x` and the configuration for the `thumbv6m-none-eabi` target.
+
+#![cfg_attr(target_os = "none", no_std, no_main)]
+
+use embedded_hal::blocking::delay::DelayMs;
+use rp2040_hal::{clocks, gpio, pac, Sio, Timer, Watchdog};
+use seven_segment::SevenSegmentPins;
+
+/// Runs the boot loader of the flash chip used by Raspberry Pi Pico.
+#[cfg(target_os = "none")]
+#[link_section = ".boot2"]
+#[used]
+pub static BOOT2: [u8; 256] = rp2040_boot2::BOOT_LOADER_GENERIC_03H;
+
+/// Frequency of the crystal on Raspberry Pi Pico
+const XTAL_FREQ_HZ: u32 = 12_000_000;
+
+fn run() -> ! {
+    let mut pac = pac::Peripherals::take().unwrap();
+    let mut watchdog = Watchdog::new(pac.WATCHDOG);
+    let clocks = clocks::init_clocks_and_plls(
+        XTAL_FREQ_HZ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+    let mut timer = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+    let sio = Sio::new(pac.SIO);
+    let pins = gpio::Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+    let mut display = SevenSegmentPins {
+        a: pins.gpio2.into_push_pull_output(),
+        b: pins.gpio3.into_push_pull_output(),
+        c: pins.gpio4.into_push_pull_output(),
+        d: pins.gpio5.into_push_pull_output(),
+        e: pins.gpio6.into_push_pull_output(),
+        f: pins.gpio7.into_push_pull_output(),
+        g: pins.gpio8.into_push_pull_output(),
+    }
+    .with_common_cathode();
+
+    loop {
+        for digit in 0..10 {
+            display
+                .set(digit)
+                .unwrap_or_else(|error| match error.source {});
+            timer.delay_ms(500u32);
+        }
+    }
+}
+
+#[cfg(target_os = "none")]
+#[rp2040_hal::entry]
+fn main() -> ! {
+    run()
+}
+
+#[cfg(not(target_os = "none"))]
+fn main() {
+    // The peripherals only exist on the chip, so there's nothing to run on the host.
+    let _ = run as fn() -> !;
+}
+
+#[cfg(target_os = "none")]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
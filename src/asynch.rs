@@ -0,0 +1,275 @@
+//! Support for pins that are set asynchronously
+//!
+//! `embedded-hal-async` doesn't provide a trait for output pins so this module defines one. Once
+//! you implement it for your pins (usually a thin wrapper) you can use the `*_async` methods of
+//! `SevenSegment`. The glyphs, the handling of polarity, the common pin and the observer are the
+//! same as with the blocking API.
+
+use super::{
+    NoPin, PinError, Polarity, Segment, SegmentObserver, SegmentPin, Segments, SevenSegment,
+};
+use core::future::Future;
+
+/// Output pin that is set asynchronously.
+///
+/// The pins need to implement the `SegmentPin` marker too, which is implemented automatically
+/// only for blocking pins:
+///
+/// ```
+/// use seven_segment::asynch::OutputPin;
+/// use seven_segment::SegmentPin;
+///
+/// struct Pin;
+///
+/// impl SegmentPin for Pin {}
+///
+/// impl OutputPin for Pin {
+///     type Error = core::convert::Infallible;
+///
+///     async fn set_low(&mut self) -> Result<(), Self::Error> {
+///         Ok(())
+///     }
+///
+///     async fn set_high(&mut self) -> Result<(), Self::Error> {
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait OutputPin: SegmentPin {
+    /// Error type
+    type Error;
+
+    /// Drives the pin low
+    fn set_low(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+
+    /// Drives the pin high
+    fn set_high(&mut self) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// Common pin which may not be connected, set asynchronously.
+///
+/// This trait is sealed and is only implemented for `NoPin` and `OutputPin`s with error type `E`.
+pub trait OptionalPin<E>: sealed::OptionalPin<E> {}
+
+impl<E, P: sealed::OptionalPin<E>> OptionalPin<E> for P {}
+
+mod sealed {
+    use core::future::Future;
+
+    pub trait OptionalPin<E> {
+        /// Enables or disables the digit, does nothing if the pin isn't connected.
+        fn write_common(
+            &mut self,
+            enabled: bool,
+            active_low: bool,
+        ) -> impl Future<Output = Result<(), E>>;
+    }
+
+    impl<E> OptionalPin<E> for super::NoPin {
+        async fn write_common(&mut self, _: bool, _: bool) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    impl<P: super::OutputPin> OptionalPin<P::Error> for P {
+        async fn write_common(&mut self, enabled: bool, active_low: bool) -> Result<(), P::Error> {
+            if enabled == active_low {
+                self.set_low().await
+            } else {
+                self.set_high().await
+            }
+        }
+    }
+}
+
+/// Drives the pin to the level that makes the segment lit or unlit.
+async fn write_segment<P: OutputPin>(pin: &mut P, on: bool, cathode: bool) -> Result<(), P::Error> {
+    if on == cathode {
+        pin.set_high().await
+    } else {
+        pin.set_low().await
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    /// Sets the value of the display asynchronously.
+    ///
+    /// This behaves the same as `set`.
+    pub async fn set_async(&mut self, value: u8) -> Result<(), PinError<A::Error>> {
+        super::check_value(value, self.max_value());
+        self.set_segments_async(self.glyph(value)).await?;
+        if value <= self.max_value() {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Lights exactly the given segments asynchronously.
+    ///
+    /// This behaves the same as `set_segments`.
+    pub async fn set_segments_async(
+        &mut self,
+        segments: Segments,
+    ) -> Result<(), PinError<A::Error>> {
+        self.state.value = None;
+        self.state.segments = segments;
+        let segments = match self.begin_write(segments) {
+            Some(segments) => segments,
+            None => return Ok(()),
+        };
+        let cathode = Common::is_cathode(self.state.polarity);
+        let inverted = self.state.inverted;
+        let active_low = Common::is_common_active_low(self.state.polarity);
+        let lit = segments != Segments::default();
+        if !lit {
+            self.common_pin
+                .write_common(false, active_low)
+                .await
+                .map_err(PinError::at(Segment::Common))?;
+        }
+        write_segment(&mut self.a, segments.a, cathode != inverted.a)
+            .await
+            .map_err(PinError::at(Segment::A))?;
+        write_segment(&mut self.b, segments.b, cathode != inverted.b)
+            .await
+            .map_err(PinError::at(Segment::B))?;
+        write_segment(&mut self.c, segments.c, cathode != inverted.c)
+            .await
+            .map_err(PinError::at(Segment::C))?;
+        write_segment(&mut self.d, segments.d, cathode != inverted.d)
+            .await
+            .map_err(PinError::at(Segment::D))?;
+        write_segment(&mut self.e, segments.e, cathode != inverted.e)
+            .await
+            .map_err(PinError::at(Segment::E))?;
+        write_segment(&mut self.f, segments.f, cathode != inverted.f)
+            .await
+            .map_err(PinError::at(Segment::F))?;
+        write_segment(&mut self.g, segments.g, cathode != inverted.g)
+            .await
+            .map_err(PinError::at(Segment::G))?;
+        if lit {
+            self.common_pin
+                .write_common(true, active_low)
+                .await
+                .map_err(PinError::at(Segment::Common))?;
+        }
+        Ok(())
+    }
+
+    /// Turns off all segments asynchronously.
+    ///
+    /// This behaves the same as `blank`.
+    pub async fn blank_async(&mut self) -> Result<(), PinError<A::Error>> {
+        self.set_segments_async(Segments::default()).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    struct TestPin<'a>(&'a mut u8);
+
+    impl crate::SegmentPin for TestPin<'_> {}
+
+    impl super::OutputPin for TestPin<'_> {
+        type Error = core::convert::Infallible;
+
+        async fn set_low(&mut self) -> Result<(), Self::Error> {
+            *self.0 = 0;
+            Ok(())
+        }
+
+        async fn set_high(&mut self) -> Result<(), Self::Error> {
+            *self.0 = 1;
+            Ok(())
+        }
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Returns a waker doing nothing as `Waker::noop` is too new for the supported Rust version.
+    pub(crate) fn noop_waker() -> Waker {
+        Arc::new(NoopWaker).into()
+    }
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+                return result;
+            }
+        }
+    }
+
+    #[test]
+    fn set_async() {
+        let mut levels = [2; 7];
+        {
+            let [a, b, c, d, e, f, g] = &mut levels;
+            let mut display = super::super::SevenSegmentPins {
+                a: TestPin(a),
+                b: TestPin(b),
+                c: TestPin(c),
+                d: TestPin(d),
+                e: TestPin(e),
+                f: TestPin(f),
+                g: TestPin(g),
+            }
+            .with_common_anode();
+            block_on(display.set_async(4)).unwrap();
+            assert_eq!(display.current(), Some(4));
+        }
+        assert_eq!(levels, [1, 0, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn common_pin_and_observer() {
+        use crate::Segments;
+
+        let mut levels = [2; 8];
+        let mut observed = Segments::default();
+        {
+            let [a, b, c, d, e, f, g, common] = &mut levels;
+            let mut display = super::super::SevenSegmentPins {
+                a: TestPin(a),
+                b: TestPin(b),
+                c: TestPin(c),
+                d: TestPin(d),
+                e: TestPin(e),
+                f: TestPin(f),
+                g: TestPin(g),
+            }
+            .with_common_cathode()
+            .with_common_pin(TestPin(common))
+            .with_observer(|segments| observed = segments);
+            block_on(display.set_async(1)).unwrap();
+        }
+        assert_eq!(levels, [0, 1, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(observed, Segments::DIGITS[1]);
+    }
+}
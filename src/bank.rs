@@ -0,0 +1,233 @@
+//! Separate displays mixed with indicator positions, e.g. clock modules
+//!
+//! Many 4-digit clock modules have a colon between the second and the third digit. `Bank` keeps
+//! both kinds of positions in the order in which they are mounted so that the digits can be set
+//! from a slice without special-casing the colon.
+
+use super::number::{render_unsigned, OVER_RANGE};
+use super::{DynamicPolarity, OutputPin, Polarity, PolarityKind, SegmentSink, StaticPolarity};
+
+/// Position of the bank, either a digit or an indicator such as the colon.
+pub enum Position<D, P> {
+    /// Display showing a digit
+    Digit(D),
+    /// Pin driving the colon or other indicator, it's driven according to the polarity of the
+    /// bank just like a segment
+    Colon(P),
+}
+
+/// Displays and indicators mounted next to each other.
+///
+/// This is intended for displays which are not multiplexed, so each position has its own pins.
+/// The digits can be any `SegmentSink`. `Common` is the polarity of the module which determines
+/// the level lighting the colons, e.g. for `Anode` they are lit by driving their pins low.
+pub struct Bank<D, P, Common, const N: usize> {
+    common: core::marker::PhantomData<fn() -> Common>,
+    polarity: PolarityKind,
+    positions: [Position<D, P>; N],
+}
+
+impl<D, P, Common: StaticPolarity, const N: usize> Bank<D, P, Common, N> {
+    /// Creates the bank from the positions ordered from the left.
+    pub fn new(positions: [Position<D, P>; N]) -> Self {
+        Self::with_polarity_kind(positions, Common::KIND)
+    }
+}
+
+impl<D, P, const N: usize> Bank<D, P, DynamicPolarity, N> {
+    /// Creates the bank with polarity chosen at runtime.
+    pub fn with_polarity(positions: [Position<D, P>; N], polarity: PolarityKind) -> Self {
+        Self::with_polarity_kind(positions, polarity)
+    }
+}
+
+impl<D, P, Common, const N: usize> Bank<D, P, Common, N> {
+    fn with_polarity_kind(positions: [Position<D, P>; N], polarity: PolarityKind) -> Self {
+        Bank {
+            common: Default::default(),
+            polarity,
+            positions,
+        }
+    }
+
+    /// Returns the positions of the bank.
+    pub fn positions(&mut self) -> &mut [Position<D, P>; N] {
+        &mut self.positions
+    }
+
+    /// Deconstructs the bank and returns the positions.
+    pub fn into_inner(self) -> [Position<D, P>; N] {
+        self.positions
+    }
+}
+
+impl<D: SegmentSink, P: OutputPin, Common: Polarity, const N: usize> Bank<D, P, Common, N> {
+    /// Shows the digits on the digit positions, skipping the colons.
+    ///
+    /// The values are shown the same way `SegmentSink::set_digit` shows them. The digit positions
+    /// beyond the end of a shorter slice are blank, the extra values of a longer slice are ignored.
+    pub fn set_slice(&mut self, digits: &[u8]) -> Result<(), BankError<D::Error, P::Error>> {
+        let mut digits = digits.iter();
+        for position in &mut self.positions {
+            if let Position::Digit(display) = position {
+                match digits.next() {
+                    Some(digit) => display.set_digit(*digit),
+                    None => display.blank(),
+                }
+                .map_err(BankError::Digit)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows the number on the digit positions with leading zeros blank, skipping the colons.
+    ///
+    /// The digits are shown using `SegmentSink::set_digit`. Returns `Ok(false)` if the number
+    /// doesn't fit, the top segments of all digits are then lit. See `number::render_unsigned` for
+    /// details.
+    pub fn set_number(
+        &mut self,
+        value: u32,
+        keep_zero: bool,
+    ) -> Result<bool, BankError<D::Error, P::Error>> {
+        let count = self
+            .positions
+            .iter()
+            .filter(|position| matches!(position, Position::Digit(_)))
+            .count();
+        let mut digits = [None; N];
+        let fits = render_unsigned(value, keep_zero, &mut digits[..count]);
+        let mut digits = digits.iter();
+        for position in &mut self.positions {
+            if let Position::Digit(display) = position {
+                match digits.next().copied().flatten() {
+                    Some(digit) => display.set_digit(digit),
+                    None if fits => display.blank(),
+                    None => display.set_segments(OVER_RANGE),
+                }
+                .map_err(BankError::Digit)?;
+            }
+        }
+        Ok(fits)
+    }
+
+    /// Lights or turns off all colon positions.
+    ///
+    /// The pins are driven according to the polarity of the bank.
+    pub fn set_colon(&mut self, on: bool) -> Result<(), BankError<D::Error, P::Error>> {
+        let cathode = Common::is_cathode(self.polarity);
+        for position in &mut self.positions {
+            if let Position::Colon(pin) = position {
+                super::write_segment(pin, on, cathode).map_err(BankError::Colon)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when updating the bank fails.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BankError<D, P> {
+    /// Setting a digit failed.
+    Digit(D),
+    /// Setting a colon pin failed.
+    Colon(P),
+}
+
+impl<D: core::fmt::Display, P: core::fmt::Display> core::fmt::Display for BankError<D, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BankError::Digit(error) => write!(f, "failed to set a digit: {}", error),
+            BankError::Colon(error) => write!(f, "failed to set the colon: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bank, Position};
+    use crate::mock::{MockDisplay, MockPin, MockSevenSegment};
+    use crate::{Anode, Cathode, PolarityKind};
+
+    #[test]
+    fn clock() {
+        let digit = || Position::Digit(MockDisplay::<Cathode>::new().into_inner());
+        let mut bank = Bank::<_, _, Cathode, 5>::new([
+            digit(),
+            digit(),
+            Position::Colon(MockPin::default()),
+            digit(),
+            digit(),
+        ]);
+        bank.set_slice(&[1, 2, 3]).unwrap();
+        bank.set_colon(true).unwrap();
+
+        let mut shown = [None; 5];
+        for (current, position) in shown.iter_mut().zip(bank.positions()) {
+            *current = match position {
+                Position::Digit(display) => display.current(),
+                Position::Colon(pin) => pin.is_high().map(u8::from),
+            };
+        }
+        assert_eq!(shown, [Some(1), Some(2), Some(1), Some(3), None]);
+    }
+
+    #[test]
+    fn set_number() {
+        use crate::Segments;
+
+        let digit = || Position::Digit(MockDisplay::<Cathode>::new().into_inner());
+        let mut bank = Bank::<_, _, Cathode, 5>::new([
+            digit(),
+            digit(),
+            Position::Colon(MockPin::default()),
+            digit(),
+            digit(),
+        ]);
+        // The colon is never written so it's shown as `None`
+        fn shown(
+            bank: &mut Bank<MockSevenSegment<Cathode>, MockPin, Cathode, 5>,
+        ) -> [Option<Segments>; 5] {
+            let mut shown = [None; 5];
+            for (current, position) in shown.iter_mut().zip(bank.positions()) {
+                *current = match position {
+                    Position::Digit(display) => Some(display.segments()),
+                    Position::Colon(pin) => pin.is_high().map(|_| Segments::default()),
+                };
+            }
+            shown
+        }
+        let blank = Some(Segments::default());
+        let d = Segments::DIGITS.map(Some);
+
+        assert_eq!(bank.set_number(0, true), Ok(true));
+        assert_eq!(shown(&mut bank), [blank, blank, None, blank, d[0]]);
+        assert_eq!(bank.set_number(0, false), Ok(true));
+        assert_eq!(shown(&mut bank), [blank, blank, None, blank, blank]);
+        assert_eq!(bank.set_number(1234, false), Ok(true));
+        assert_eq!(shown(&mut bank), [d[1], d[2], None, d[3], d[4]]);
+        assert_eq!(bank.set_number(12345, false), Ok(false));
+        let over = Some(Segments::from_bits(1));
+        assert_eq!(shown(&mut bank), [over, over, None, over, over]);
+    }
+
+    #[test]
+    fn colon_polarity() {
+        let digit = || Position::Digit(MockDisplay::<Anode>::new().into_inner());
+        let mut bank = Bank::<_, _, Anode, 2>::new([digit(), Position::Colon(MockPin::default())]);
+        bank.set_colon(true).unwrap();
+        match bank.positions() {
+            [_, Position::Colon(pin)] => assert_eq!(pin.is_high(), Some(false)),
+            _ => unreachable!(),
+        }
+
+        let colon = Position::<MockSevenSegment<Anode>, _>::Colon(MockPin::default());
+        let mut bank = Bank::with_polarity([colon], PolarityKind::Anode);
+        bank.set_colon(false).unwrap();
+        match bank.into_inner() {
+            [Position::Colon(pin)] => assert_eq!(pin.is_high(), Some(true)),
+            _ => unreachable!(),
+        }
+    }
+}
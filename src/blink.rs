@@ -0,0 +1,127 @@
+//! Blinking digit, e.g. to indicate editing
+
+use super::SegmentSink;
+
+/// Alternately shows the value and blanks the display.
+///
+/// The timing is measured in calls to `tick` so you need to call it periodically, e.g. from a
+/// timer interrupt. The value is shown for `on_ticks` calls and the display is blank for
+/// `off_ticks` calls. The pins are only written when the state changes. Any `SegmentSink` can
+/// be used as the display.
+pub struct Blink<D> {
+    display: D,
+    value: u8,
+    on_ticks: u16,
+    off_ticks: u16,
+    phase: u16,
+}
+
+impl<D> Blink<D> {
+    /// Creates the blinking display, the value is shown on the next `tick`.
+    pub fn new(display: D, value: u8, on_ticks: u16, off_ticks: u16) -> Self {
+        Blink {
+            display,
+            value,
+            on_ticks,
+            off_ticks,
+            phase: 0,
+        }
+    }
+
+    /// Changes the blinking value.
+    ///
+    /// The cycle is restarted so the new value is shown on the next `tick`.
+    pub fn set_value(&mut self, value: u8) {
+        self.value = value;
+        self.phase = 0;
+    }
+
+    /// Changes for how many ticks the value is shown and for how many the display is blank.
+    ///
+    /// The cycle is restarted so the value is shown on the next `tick`.
+    pub fn set_period(&mut self, on_ticks: u16, off_ticks: u16) {
+        self.on_ticks = on_ticks;
+        self.off_ticks = off_ticks;
+        self.phase = 0;
+    }
+
+    /// Returns the blinking value.
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+
+    /// Returns the wrapped display.
+    pub fn into_inner(self) -> D {
+        self.display
+    }
+}
+
+impl<D: SegmentSink> Blink<D> {
+    /// Advances the blinking by one tick.
+    pub fn tick(&mut self) -> Result<(), D::Error> {
+        let result = if self.phase == 0 || self.phase == self.on_ticks {
+            if self.phase < self.on_ticks {
+                self.display.set_digit(self.value)
+            } else {
+                self.display.blank()
+            }
+        } else {
+            Ok(())
+        };
+
+        self.phase += 1;
+        if self.phase >= self.on_ticks.saturating_add(self.off_ticks) {
+            self.phase = 0;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Blink;
+    use crate::mock::MockDisplay;
+    use crate::Cathode;
+
+    #[test]
+    fn cycle() {
+        let mut blink = Blink::new(MockDisplay::<Cathode>::new().into_inner(), 1, 2, 1);
+        let mut shown = [None; 7];
+        for current in &mut shown {
+            blink.tick().unwrap();
+            *current = blink.display.current();
+        }
+        assert_eq!(
+            shown,
+            [Some(1), Some(1), None, Some(1), Some(1), None, Some(1)]
+        );
+
+        blink.set_value(4);
+        blink.set_period(1, 2);
+        for current in &mut shown {
+            blink.tick().unwrap();
+            *current = blink.display.current();
+        }
+        assert_eq!(shown, [Some(4), None, None, Some(4), None, None, Some(4)]);
+    }
+
+    #[test]
+    fn any_sink() {
+        use crate::mock::MockDisplay;
+        use crate::multiplex::MultiplexedDisplay;
+        use crate::{Cathode, SegmentSink, Segments};
+        use core::convert::Infallible;
+
+        let segments = MockDisplay::<Cathode>::new().into_inner();
+        let mut display = MultiplexedDisplay::new(segments, [(), ()]);
+        {
+            let sink: &mut dyn SegmentSink<Error = Infallible> = &mut display;
+            let mut blink = Blink::new(sink, 7, 1, 1);
+            blink.tick().unwrap();
+        }
+        assert_eq!(
+            display.buffer(),
+            &[Segments::default(), Segments::DIGITS[7]]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Single digit counting down, e.g. for timers
+
+use super::SegmentSink;
+
+/// Counts down from the start value to zero, showing the remaining value.
+///
+/// Call `restart` to show the start value and then `tick_second` every second, e.g. from a timer
+/// interrupt. The start value should be at most 9, larger values are shown the same way `set`
+/// shows them.
+pub struct Countdown<D> {
+    display: D,
+    start: u8,
+    remaining: u8,
+}
+
+impl<D> Countdown<D> {
+    /// Creates the countdown, the display is not written until `restart` or `tick_second`.
+    pub fn new(display: D, start: u8) -> Self {
+        Countdown {
+            display,
+            start,
+            remaining: start,
+        }
+    }
+
+    /// Returns the value currently being shown.
+    pub fn remaining(&self) -> u8 {
+        self.remaining
+    }
+
+    /// Returns `true` if the countdown reached zero.
+    pub fn is_finished(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Returns the wrapped display.
+    pub fn into_inner(self) -> D {
+        self.display
+    }
+}
+
+impl<D: SegmentSink> Countdown<D> {
+    /// Starts counting from the start value again and shows it.
+    pub fn restart(&mut self) -> Result<(), D::Error> {
+        self.remaining = self.start;
+        self.display.set_digit(self.remaining)
+    }
+
+    /// Decrements the value and shows it.
+    ///
+    /// Returns `true` when the countdown reached zero. Once it's finished calling this does
+    /// nothing and keeps returning `true`.
+    pub fn tick_second(&mut self) -> Result<bool, D::Error> {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            self.display.set_digit(self.remaining)?;
+        }
+        Ok(self.is_finished())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Countdown;
+    use crate::mock::MockDisplay;
+    use crate::Anode;
+
+    #[test]
+    fn count_down() {
+        let mut countdown = Countdown::new(MockDisplay::<Anode>::new().into_inner(), 3);
+        countdown.restart().unwrap();
+        assert_eq!(countdown.display.current(), Some(3));
+        assert_eq!(countdown.tick_second(), Ok(false));
+        assert_eq!(countdown.tick_second(), Ok(false));
+        assert_eq!(countdown.display.current(), Some(1));
+        assert_eq!(countdown.tick_second(), Ok(true));
+        assert_eq!(countdown.display.current(), Some(0));
+        assert_eq!(countdown.tick_second(), Ok(true));
+        assert_eq!(countdown.remaining(), 0);
+
+        countdown.restart().unwrap();
+        assert_eq!(countdown.display.current(), Some(3));
+        assert!(!countdown.is_finished());
+    }
+}
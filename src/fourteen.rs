@@ -0,0 +1,248 @@
+//! Driver for 14-segment alphanumeric displays
+//!
+//! 14-segment displays can show the whole uppercase alphabet. Since they have so many pins, all
+//! pins are required to have the same type. Most HALs provide type-erased pins for this purpose.
+
+use super::{
+    write_segment, DynamicPolarity, OutputPin, Polarity, PolarityKind, SetError, StaticPolarity,
+    UnsupportedChar,
+};
+
+/// Pins of the 14-segment display
+///
+/// Pin arrangement:
+/// ```text
+///  ___a___
+/// |\  |  /|
+/// f h j k b
+/// |  \|/  |
+///  -g1-g2-
+/// |  /|\  |
+/// e l m n c
+/// |/  |  \|
+///  ---d---
+/// ```
+pub struct FourteenSegmentPins<P> {
+    /// Upper horizontal bar
+    pub a: P,
+    /// Upper right vertical bar
+    pub b: P,
+    /// Lower right vertical bar
+    pub c: P,
+    /// Lower horizontal bar
+    pub d: P,
+    /// Lower left vertical bar
+    pub e: P,
+    /// Upper left vertical bar
+    pub f: P,
+    /// Left half of the middle horizontal bar
+    pub g1: P,
+    /// Right half of the middle horizontal bar
+    pub g2: P,
+    /// Upper left diagonal bar
+    pub h: P,
+    /// Upper middle vertical bar
+    pub j: P,
+    /// Upper right diagonal bar
+    pub k: P,
+    /// Lower left diagonal bar
+    pub l: P,
+    /// Lower middle vertical bar
+    pub m: P,
+    /// Lower right diagonal bar
+    pub n: P,
+}
+
+impl<P> FourteenSegmentPins<P> {
+    /// Constructs `FourteenSegment` with specified polarity.
+    #[must_use]
+    pub fn with_common<Common: StaticPolarity>(self) -> FourteenSegment<P, Common> {
+        FourteenSegment::new(self, Common::KIND)
+    }
+
+    /// Shorthand for `with_common::<Cathode>()`.
+    #[must_use]
+    pub fn with_common_cathode(self) -> FourteenSegment<P, super::Cathode> {
+        self.with_common()
+    }
+
+    /// Shorthand for `with_common::<Anode>()`.
+    #[must_use]
+    pub fn with_common_anode(self) -> FourteenSegment<P, super::Anode> {
+        self.with_common()
+    }
+
+    /// Constructs `FourteenSegment` with polarity chosen at runtime.
+    #[must_use]
+    pub fn with_polarity(self, polarity: PolarityKind) -> FourteenSegment<P, DynamicPolarity> {
+        FourteenSegment::new(self, polarity)
+    }
+}
+
+/// Represents 14-segment display.
+///
+/// Use `FourteenSegmentPins` to construct it.
+pub struct FourteenSegment<P, Common> {
+    common: core::marker::PhantomData<fn() -> Common>,
+    polarity: PolarityKind,
+    // in the order of bits - a, b, c, d, e, f, g1, g2, h, j, k, l, m, n
+    pins: [P; 14],
+}
+
+impl<P, Common> FourteenSegment<P, Common> {
+    fn new(pins: FourteenSegmentPins<P>, polarity: PolarityKind) -> Self {
+        FourteenSegment {
+            common: Default::default(),
+            polarity,
+            pins: [
+                pins.a, pins.b, pins.c, pins.d, pins.e, pins.f, pins.g1, pins.g2, pins.h, pins.j,
+                pins.k, pins.l, pins.m, pins.n,
+            ],
+        }
+    }
+
+    /// Deconstructs the display and returns the pins.
+    pub fn into_pins(self) -> FourteenSegmentPins<P> {
+        let [a, b, c, d, e, f, g1, g2, h, j, k, l, m, n] = self.pins;
+        FourteenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g1,
+            g2,
+            h,
+            j,
+            k,
+            l,
+            m,
+            n,
+        }
+    }
+}
+
+impl<P: OutputPin, Common: Polarity> FourteenSegment<P, Common> {
+    /// Lights exactly the given segments.
+    ///
+    /// Bits 0 to 13 correspond to segments `a`, `b`, `c`, `d`, `e`, `f`, `g1`, `g2`, `h`, `j`,
+    /// `k`, `l`, `m`, `n` in this order, set bit means the segment is lit. This is the same order
+    /// as used by common font tables for these displays. Bits 14 and 15 are ignored.
+    pub fn set_segments(&mut self, segments: u16) -> Result<(), P::Error> {
+        let cathode = Common::is_cathode(self.polarity);
+        for (i, pin) in self.pins.iter_mut().enumerate() {
+            write_segment(pin, segments & (1 << i) != 0, cathode)?;
+        }
+        Ok(())
+    }
+
+    /// Shows the character on the display.
+    ///
+    /// See `glyph` for the list of supported characters. If the character is not supported the
+    /// display is left unchanged and an error is returned.
+    pub fn set_char(&mut self, c: char) -> Result<(), SetError<UnsupportedChar, P::Error>> {
+        let segments = glyph(c).ok_or(SetError::Invalid(UnsupportedChar(c)))?;
+        self.set_segments(segments).map_err(SetError::Pin)
+    }
+
+    /// Turns off all segments.
+    pub fn blank(&mut self) -> Result<(), P::Error> {
+        self.set_segments(0)
+    }
+}
+
+/// Returns the segments needed to show the character.
+///
+/// Digits, letters, space and `-` are supported. Lower case letters are shown as upper case. See
+/// `FourteenSegment::set_segments` for the meaning of bits.
+pub const fn glyph(c: char) -> Option<u16> {
+    let segments = match c.to_ascii_uppercase() {
+        ' ' => 0b0000000000000000,
+        '-' => 0b0000000011000000,
+        '0' => 0b0000110000111111,
+        '1' => 0b0000000000000110,
+        '2' => 0b0000000011011011,
+        '3' => 0b0000000010001111,
+        '4' => 0b0000000011100110,
+        '5' => 0b0010000001101001,
+        '6' => 0b0000000011111101,
+        '7' => 0b0000000000000111,
+        '8' => 0b0000000011111111,
+        '9' => 0b0000000011101111,
+        'A' => 0b0000000011110111,
+        'B' => 0b0001001010001111,
+        'C' => 0b0000000000111001,
+        'D' => 0b0001001000001111,
+        'E' => 0b0000000011111001,
+        'F' => 0b0000000001110001,
+        'G' => 0b0000000010111101,
+        'H' => 0b0000000011110110,
+        'I' => 0b0001001000001001,
+        'J' => 0b0000000000011110,
+        'K' => 0b0010010001110000,
+        'L' => 0b0000000000111000,
+        'M' => 0b0000010100110110,
+        'N' => 0b0010000100110110,
+        'O' => 0b0000000000111111,
+        'P' => 0b0000000011110011,
+        'Q' => 0b0010000000111111,
+        'R' => 0b0010000011110011,
+        'S' => 0b0000000011101101,
+        'T' => 0b0001001000000001,
+        'U' => 0b0000000000111110,
+        'V' => 0b0000110000110000,
+        'W' => 0b0010100000110110,
+        'X' => 0b0010110100000000,
+        'Y' => 0b0001010100000000,
+        'Z' => 0b0000110000001001,
+        _ => return None,
+    };
+    Some(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::TestPin;
+
+    fn levels(c: char) -> u16 {
+        let mut pins: [TestPin; 14] = core::array::from_fn(|_| TestPin(2));
+        {
+            let [a, b, c_, d, e, f, g1, g2, h, j, k, l, m, n] = &mut pins;
+            let mut display = super::FourteenSegmentPins {
+                a,
+                b,
+                c: c_,
+                d,
+                e,
+                f,
+                g1,
+                g2,
+                h,
+                j,
+                k,
+                l,
+                m,
+                n,
+            }
+            .with_common_anode();
+            display.set_char(c).unwrap();
+        }
+        pins.iter()
+            .enumerate()
+            .map(|(i, pin)| u16::from(pin.inv()) << i)
+            .sum()
+    }
+
+    #[test]
+    fn alphabet() {
+        for c in 'A'..='Z' {
+            assert_eq!(Some(levels(c)), super::glyph(c));
+            assert_eq!(levels(c.to_ascii_lowercase()), levels(c));
+            assert_ne!(levels(c), 0);
+        }
+        assert_eq!(levels('T'), 0b0001001000000001);
+        assert_eq!(levels(' '), 0);
+        assert_eq!(super::glyph('%'), None);
+    }
+}
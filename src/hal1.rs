@@ -0,0 +1,121 @@
+//! Support for pins implementing embedded-hal 1.0
+//!
+//! The driver is implemented on top of `OutputPin` from embedded-hal 0.2. Pins implementing the
+//! 1.0 version of the trait can be used by wrapping them in `Pin`. Errors returned by the pins are
+//! propagated as-is.
+
+/// Adapter for a pin implementing `embedded_hal::digital::OutputPin` version 1.0
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Pin<P>(pub P);
+
+impl<P> Pin<P> {
+    /// Returns the wrapped pin.
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P: embedded_hal_1::digital::OutputPin> super::OutputPin for Pin<P> {
+    type Error = P::Error;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high()
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low()
+    }
+}
+
+impl<A, B, C, D, E, F, G>
+    super::SevenSegmentPins<Pin<A>, Pin<B>, Pin<C>, Pin<D>, Pin<E>, Pin<F>, Pin<G>>
+{
+    /// Wraps pins implementing embedded-hal 1.0.
+    pub fn from_hal1(pins: super::SevenSegmentPins<A, B, C, D, E, F, G>) -> Self {
+        super::SevenSegmentPins {
+            a: Pin(pins.a),
+            b: Pin(pins.b),
+            c: Pin(pins.c),
+            d: Pin(pins.d),
+            e: Pin(pins.e),
+            f: Pin(pins.f),
+            g: Pin(pins.g),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_1::digital::{ErrorKind, ErrorType, OutputPin};
+
+    #[derive(Debug)]
+    struct Error;
+
+    impl embedded_hal_1::digital::Error for Error {
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    struct TestPin<'a>(&'a mut Option<bool>);
+
+    impl ErrorType for TestPin<'_> {
+        type Error = Error;
+    }
+
+    impl OutputPin for TestPin<'_> {
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            match self.0 {
+                Some(_) => Err(Error),
+                None => {
+                    *self.0 = Some(true);
+                    Ok(())
+                }
+            }
+        }
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            match self.0 {
+                Some(_) => Err(Error),
+                None => {
+                    *self.0 = Some(false);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut levels = [None; 7];
+        let [a, b, c, d, e, f, g] = &mut levels;
+        let pins = super::super::SevenSegmentPins {
+            a: TestPin(a),
+            b: TestPin(b),
+            c: TestPin(c),
+            d: TestPin(d),
+            e: TestPin(e),
+            f: TestPin(f),
+            g: TestPin(g),
+        };
+        {
+            let mut display = super::super::SevenSegmentPins::from_hal1(pins).with_common_cathode();
+            display.set(1).unwrap();
+            // The test pins refuse to be set twice
+            display.set(1).unwrap_err();
+        }
+
+        assert_eq!(
+            levels,
+            [
+                Some(false),
+                Some(true),
+                Some(true),
+                Some(false),
+                Some(false),
+                Some(false),
+                Some(false)
+            ]
+        );
+    }
+}
@@ -3,10 +3,11 @@
 //! This is a driver (encoder) for 7-segment displays. It's implemented on top of embedded-hal, so
 //! you can use it on any platform that has pins with `embedded_hal::OutputPin` implemented.
 //!
-//! The driver is very simple, only supports displays that connect directly using seven pins such
-//! as [SA52-11EWA](http://www.kingbrightusa.com/images/catalog/SPEC/SA52-11EWA.pdf) and doesn't
-//! try to do anything clever like setting all pins at once. It supports both common anode and
-//! common cathode displays.
+//! The driver is very simple, it's intended for displays that connect directly using seven pins
+//! such as [SA52-11EWA](http://www.kingbrightusa.com/images/catalog/SPEC/SA52-11EWA.pdf) and sets
+//! the pins one by one. If the segments are connected to a port which can set all pins at once,
+//! such as an I2C expander or a shift register, use `port::PortSevenSegment` to write them in a
+//! single transaction. It supports both common anode and common cathode displays.
 //!
 //! In order to use this crate, you have to instantiate `SevenSegmentPins` with your pins (see its
 //! documentation for a diagram) and convert it by calling appropriate `with_common_*()` method.
@@ -17,46 +18,447 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
 pub use embedded_hal::digital::v2::OutputPin;
 
+use core::convert::TryInto;
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+
+#[cfg(feature = "async")]
+pub mod asynch;
+pub mod bank;
+pub mod blink;
+pub mod countdown;
+pub mod fourteen;
+#[cfg(feature = "hal1")]
+pub mod hal1;
+pub mod marquee;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod multiplex;
+pub mod number;
+pub mod pair;
+pub mod port;
+#[cfg(feature = "stateful")]
+pub mod stateful;
+
+/// Constructs the display from labeled pins.
+///
+/// The first argument is the polarity, `anode` or `cathode`, followed by the pins `a` to `g` in
+/// this order and optionally the decimal point `dp`. If all pins have the same type they can be
+/// passed as an array in `a` to `g` order instead: `seven_segment!(cathode, array: pins)`.
+///
+/// ```
+/// use seven_segment::seven_segment;
+/// # use seven_segment::OutputPin;
+/// # struct Pin;
+/// # impl OutputPin for Pin {
+/// #     type Error = core::convert::Infallible;
+/// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// let mut display = seven_segment!(cathode, a: Pin, b: Pin, c: Pin, d: Pin, e: Pin, f: Pin, g: Pin);
+/// display.set(4).unwrap();
+///
+/// let mut display = seven_segment!(
+///     anode,
+///     a: Pin,
+///     b: Pin,
+///     c: Pin,
+///     d: Pin,
+///     e: Pin,
+///     f: Pin,
+///     g: Pin,
+///     dp: Pin,
+/// );
+/// display.set_with_dp(4, true).unwrap();
+///
+/// let mut display = seven_segment!(anode, array: [Pin, Pin, Pin, Pin, Pin, Pin, Pin]);
+/// display.set(4).unwrap();
+/// ```
+#[macro_export]
+macro_rules! seven_segment {
+    (@common anode, $pins:expr) => {
+        $pins.with_common_anode()
+    };
+    (@common cathode, $pins:expr) => {
+        $pins.with_common_cathode()
+    };
+    ($common:ident, array: $pins:expr $(,)?) => {
+        $crate::seven_segment!(@common $common, $crate::SevenSegmentPins::from_array($pins))
+    };
+    (
+        $common:ident,
+        a: $a:expr,
+        b: $b:expr,
+        c: $c:expr,
+        d: $d:expr,
+        e: $e:expr,
+        f: $f:expr,
+        g: $g:expr,
+        dp: $dp:expr $(,)?
+    ) => {
+        $crate::seven_segment!(
+            @common $common,
+            $crate::SevenSegmentPinsWithDp {
+                a: $a,
+                b: $b,
+                c: $c,
+                d: $d,
+                e: $e,
+                f: $f,
+                g: $g,
+                dp: $dp,
+            }
+        )
+    };
+    (
+        $common:ident,
+        a: $a:expr,
+        b: $b:expr,
+        c: $c:expr,
+        d: $d:expr,
+        e: $e:expr,
+        f: $f:expr,
+        g: $g:expr $(,)?
+    ) => {
+        $crate::seven_segment!(
+            @common $common,
+            $crate::SevenSegmentPins {
+                a: $a,
+                b: $b,
+                c: $c,
+                d: $d,
+                e: $e,
+                f: $f,
+                g: $g,
+            }
+        )
+    };
+}
+
 /// Type erased definitions
 pub mod erased {
     /// An alias for SevenSegment which has all pins of the same type.
     pub type SevenSegment<T, Common> = super::SevenSegment<T, T, T, T, T, T, T, Common>;
+
+    /// An alias for SevenSegment with decimal point which has all pins of the same type.
+    pub type SevenSegmentWithDp<T, Common> = super::SevenSegment<T, T, T, T, T, T, T, Common, T>;
+
+    /// Constructs the display from an array of pins in `a` to `g` order.
+    ///
+    /// This is convenient if the HAL provides the pins as an array. The polarity is chosen at
+    /// runtime, if you know it at compile time destructure the array and use `from_pins`.
+    pub fn from_array<T: super::SegmentPin>(
+        pins: [T; 7],
+        polarity: super::PolarityKind,
+    ) -> SevenSegment<T, super::DynamicPolarity> {
+        super::SevenSegmentPins::from_array(pins).with_polarity(polarity)
+    }
 }
 
 mod sealed {
+    use super::PolarityKind;
+
     pub trait Polarity {
-        fn is_cathode() -> bool;
+        /// Returns `true` for common cathode, `polarity` is the one stored in the display.
+        fn is_cathode(polarity: PolarityKind) -> bool;
+
+        /// Returns `true` if the digit is enabled by driving the common pin low.
+        fn is_common_active_low(polarity: PolarityKind) -> bool;
+    }
+
+    pub trait StaticPolarity: Polarity {
+        const KIND: PolarityKind;
     }
 
     impl Polarity for super::Anode {
-        fn is_cathode() -> bool {
+        fn is_cathode(_: PolarityKind) -> bool {
+            false
+        }
+
+        fn is_common_active_low(_: PolarityKind) -> bool {
             false
         }
     }
 
     impl Polarity for super::Cathode {
-        fn is_cathode() -> bool {
+        fn is_cathode(_: PolarityKind) -> bool {
+            true
+        }
+
+        fn is_common_active_low(_: PolarityKind) -> bool {
+            true
+        }
+    }
+
+    impl Polarity for super::CommonAnodeActiveLow {
+        fn is_cathode(_: PolarityKind) -> bool {
+            false
+        }
+
+        fn is_common_active_low(_: PolarityKind) -> bool {
+            true
+        }
+    }
+
+    impl Polarity for super::CommonCathodeActiveHigh {
+        fn is_cathode(_: PolarityKind) -> bool {
             true
         }
+
+        fn is_common_active_low(_: PolarityKind) -> bool {
+            false
+        }
+    }
+
+    impl Polarity for super::DynamicPolarity {
+        fn is_cathode(polarity: PolarityKind) -> bool {
+            polarity == PolarityKind::Cathode
+        }
+
+        fn is_common_active_low(polarity: PolarityKind) -> bool {
+            polarity == PolarityKind::Cathode
+        }
+    }
+
+    impl StaticPolarity for super::Anode {
+        const KIND: PolarityKind = PolarityKind::Anode;
+    }
+
+    impl StaticPolarity for super::Cathode {
+        const KIND: PolarityKind = PolarityKind::Cathode;
+    }
+
+    impl StaticPolarity for super::CommonAnodeActiveLow {
+        const KIND: PolarityKind = PolarityKind::Anode;
+    }
+
+    impl StaticPolarity for super::CommonCathodeActiveHigh {
+        const KIND: PolarityKind = PolarityKind::Cathode;
+    }
+
+    pub trait OptionalPin<E> {
+        /// Enables or disables the digit, does nothing if the pin isn't connected.
+        fn write_common(&mut self, enabled: bool, active_low: bool) -> Result<(), E>;
+
+        /// Lights or turns off the segment, does nothing if the pin isn't connected.
+        fn write_segment(&mut self, on: bool, cathode: bool) -> Result<(), E>;
+    }
+
+    impl<E> OptionalPin<E> for super::NoPin {
+        fn write_common(&mut self, _: bool, _: bool) -> Result<(), E> {
+            Ok(())
+        }
+
+        fn write_segment(&mut self, _: bool, _: bool) -> Result<(), E> {
+            Ok(())
+        }
+    }
+
+    pub trait AuxPins {
+        type Error;
+
+        /// Lights or turns off the indicator, returns `None` if the index is out of range.
+        fn write(
+            &mut self,
+            index: usize,
+            on: bool,
+            cathode: bool,
+        ) -> Option<Result<(), Self::Error>>;
+    }
+
+    impl<P: super::OutputPin, const N: usize> AuxPins for [P; N] {
+        type Error = P::Error;
+
+        fn write(
+            &mut self,
+            index: usize,
+            on: bool,
+            cathode: bool,
+        ) -> Option<Result<(), Self::Error>> {
+            let pin = self.get_mut(index)?;
+            Some(super::write_segment(pin, on, cathode))
+        }
+    }
+
+    impl<P: super::OutputPin> OptionalPin<P::Error> for P {
+        fn write_common(&mut self, enabled: bool, active_low: bool) -> Result<(), P::Error> {
+            super::write_common(self, enabled, active_low)
+        }
+
+        fn write_segment(&mut self, on: bool, cathode: bool) -> Result<(), P::Error> {
+            super::write_segment(self, on, cathode)
+        }
     }
 }
 
 /// Polarity of the common electrode.
 ///
-/// This trait is sealed and is only implemented for `Anode` and `Cathode` as they're all that's
-/// needed.
+/// The polarity determines both the level lighting the segments and the level enabling the digit
+/// through the common pin, if there's one. This trait is sealed and is only implemented for the
+/// marker types in this crate as they cover all practical combinations.
 pub trait Polarity: sealed::Polarity {}
 
+/// Polarity of the common electrode known at compile time.
+///
+/// This trait is sealed and is only implemented for `Anode`, `Cathode`, `CommonAnodeActiveLow` and
+/// `CommonCathodeActiveHigh`.
+pub trait StaticPolarity: Polarity + sealed::StaticPolarity {
+    /// `true` for common anode, `false` for common cathode
+    const IS_ANODE: bool;
+}
+
+/// Pin driving a segment.
+///
+/// This is implemented for all `OutputPin`s and only exists to provide a friendlier error
+/// message: the constructors require it so that a mistyped pin is reported right where the
+/// display is constructed rather than deep inside the methods setting the pins. Async pins
+/// implement it explicitly, it's a supertrait of `asynch::OutputPin`.
+///
+/// ```compile_fail,E0277
+/// use seven_segment::SevenSegmentPins;
+///
+/// struct NotAPin;
+///
+/// let pins = SevenSegmentPins {
+///     a: NotAPin,
+///     b: NotAPin,
+///     c: NotAPin,
+///     d: NotAPin,
+///     e: NotAPin,
+///     f: NotAPin,
+///     g: NotAPin,
+/// };
+/// // error: `NotAPin` can not drive a segment because it doesn't implement `OutputPin`
+/// let display = pins.with_common_cathode();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not drive a segment because it doesn't implement `OutputPin`",
+    label = "this pin doesn't implement `OutputPin` from embedded-hal 0.2",
+    note = "pins from embedded-hal 1.0 can be used with the `hal1::Pin` adapter",
+    note = "async pins need `impl SegmentPin for {Self} {{}}` next to `asynch::OutputPin`"
+)]
+pub trait SegmentPin {}
+
+impl<P: OutputPin> SegmentPin for P {}
+
+/// Pin which may not be connected, such as the decimal point or the common electrode.
+///
+/// This trait is sealed and is only implemented for `NoPin` and `OutputPin`s with error type `E`.
+pub trait OptionalPin<E>: sealed::OptionalPin<E> {}
+
+impl<E, P: sealed::OptionalPin<E>> OptionalPin<E> for P {}
+
+/// Auxiliary indicators, such as the colon or AM/PM dots of a clock.
+///
+/// This trait is sealed and is only implemented for arrays of `OutputPin`s. Displays without
+/// indicators don't have `set_aux`.
+pub trait AuxPins: sealed::AuxPins {}
+
+impl<P: sealed::AuxPins> AuxPins for P {}
+
 /// Marker type for common anode
+///
+/// The segments are lit by driving their pins low and the digit is enabled by driving the common
+/// pin high, which is the case when the common electrode is connected to the pin directly.
 pub enum Anode {}
 
 /// Marker type for common cathode
+///
+/// The segments are lit by driving their pins high and the digit is enabled by driving the common
+/// pin low, which is the case when the common electrode is connected to the pin directly.
 pub enum Cathode {}
 
+/// Marker type for common anode enabled by driving the common pin low
+///
+/// This is the case when the anode is switched by a PNP transistor. The segments are lit by
+/// driving their pins low.
+pub enum CommonAnodeActiveLow {}
+
+/// Marker type for common cathode enabled by driving the common pin high
+///
+/// This is the case when the cathode is switched by an NPN transistor. The segments are lit by
+/// driving their pins high.
+pub enum CommonCathodeActiveHigh {}
+
+/// Common anode enabled by driving the common pin high, the same as `Anode`
+pub type CommonAnodeActiveHigh = Anode;
+
+/// Common cathode enabled by driving the common pin low, the same as `Cathode`
+pub type CommonCathodeActiveLow = Cathode;
+
+/// Marker type for polarity chosen at runtime
+///
+/// This is useful if your firmware supports multiple revisions of hardware which differ in
+/// polarity. The display can be constructed using `with_polarity` and the polarity can be changed
+/// using `set_polarity`.
+pub enum DynamicPolarity {}
+
 impl Polarity for Anode {}
 impl Polarity for Cathode {}
+impl Polarity for CommonAnodeActiveLow {}
+impl Polarity for CommonCathodeActiveHigh {}
+impl Polarity for DynamicPolarity {}
+impl StaticPolarity for Anode {
+    const IS_ANODE: bool = true;
+}
+impl StaticPolarity for Cathode {
+    const IS_ANODE: bool = false;
+}
+impl StaticPolarity for CommonAnodeActiveLow {
+    const IS_ANODE: bool = true;
+}
+impl StaticPolarity for CommonCathodeActiveHigh {
+    const IS_ANODE: bool = false;
+}
+
+/// Polarity of the common electrode as a value
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PolarityKind {
+    /// Common anode - segments are lit by driving their pins low
+    Anode,
+    /// Common cathode - segments are lit by driving their pins high
+    Cathode,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Anode {
+    fn format(&self, _: defmt::Formatter<'_>) {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Cathode {
+    fn format(&self, _: defmt::Formatter<'_>) {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for CommonAnodeActiveLow {
+    fn format(&self, _: defmt::Formatter<'_>) {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for CommonCathodeActiveHigh {
+    fn format(&self, _: defmt::Formatter<'_>) {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DynamicPolarity {
+    fn format(&self, _: defmt::Formatter<'_>) {
+        match *self {}
+    }
+}
 
 /// Pins of the 7-sement display
 ///
@@ -92,25 +494,143 @@ pub struct SevenSegmentPins<A, B, C, D, E, F, G> {
 }
 
 impl<A, B, C, D, E, F, G> SevenSegmentPins<A, B, C, D, E, F, G> {
-    /// Constructs `SevenSegment` with specified polarity.
-    pub fn with_common<Common: Polarity>(self) -> SevenSegment<A, B, C, D, E, F, G, Common> {
-        SevenSegment {
-            common: Default::default(),
-            a: self.a,
-            b: self.b,
-            c: self.c,
-            d: self.d,
-            e: self.e,
-            f: self.f,
-            g: self.g,
+    /// Constructs the pins from a tuple in `a` to `g` order.
+    pub fn from_tuple(pins: (A, B, C, D, E, F, G)) -> Self {
+        let (a, b, c, d, e, f, g) = pins;
+        SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+    }
+
+    /// Borrows the pins so that a display can be constructed temporarily.
+    ///
+    /// This is useful if you want to keep the pins and only drive the display from time to time,
+    /// e.g. in a task of RTOS. Note that the display constructed this way doesn't remember the
+    /// state between the borrows.
+    ///
+    /// ```
+    /// use seven_segment::{OutputPin, PinError, SevenSegmentPins};
+    ///
+    /// fn show<P: OutputPin>(
+    ///     pins: &mut SevenSegmentPins<P, P, P, P, P, P, P>,
+    /// ) -> Result<(), PinError<P::Error>> {
+    ///     pins.by_ref().with_common_cathode().set(4)
+    /// }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn by_ref(
+        &mut self,
+    ) -> SevenSegmentPins<
+        PinRef<'_, A>,
+        PinRef<'_, B>,
+        PinRef<'_, C>,
+        PinRef<'_, D>,
+        PinRef<'_, E>,
+        PinRef<'_, F>,
+        PinRef<'_, G>,
+    > {
+        SevenSegmentPins {
+            a: PinRef(&mut self.a),
+            b: PinRef(&mut self.b),
+            c: PinRef(&mut self.c),
+            d: PinRef(&mut self.d),
+            e: PinRef(&mut self.e),
+            f: PinRef(&mut self.f),
+            g: PinRef(&mut self.g),
+        }
+    }
+
+    /// Wraps the pins in `InfalliblePin` so that the display never returns an error.
+    ///
+    /// The error can then be eliminated without a panic:
+    ///
+    /// ```
+    /// use seven_segment::{OutputPin, SevenSegmentPins};
+    ///
+    /// fn show<P: OutputPin<Error = ()>>(pins: SevenSegmentPins<P, P, P, P, P, P, P>) {
+    ///     let mut display = pins.into_infallible().with_common_cathode();
+    ///     display.set(4).unwrap_or_else(|error| match error.source {});
+    /// }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn into_infallible(
+        self,
+    ) -> SevenSegmentPins<
+        InfalliblePin<A>,
+        InfalliblePin<B>,
+        InfalliblePin<C>,
+        InfalliblePin<D>,
+        InfalliblePin<E>,
+        InfalliblePin<F>,
+        InfalliblePin<G>,
+    > {
+        SevenSegmentPins {
+            a: InfalliblePin(self.a),
+            b: InfalliblePin(self.b),
+            c: InfalliblePin(self.c),
+            d: InfalliblePin(self.d),
+            e: InfalliblePin(self.e),
+            f: InfalliblePin(self.f),
+            g: InfalliblePin(self.g),
         }
     }
+}
+
+impl<A, B, C, D, E, F, G> SevenSegmentPins<A, B, C, D, E, F, G> {
+    /// Constructs `SevenSegment` with specified polarity.
+    #[must_use]
+    pub fn with_common<Common: StaticPolarity>(self) -> SevenSegment<A, B, C, D, E, F, G, Common>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
+        SevenSegment::new(self, NoPin, Common::KIND)
+    }
+
+    /// Constructs `SevenSegment` with polarity chosen at runtime.
+    #[must_use]
+    pub fn with_polarity(
+        self,
+        polarity: PolarityKind,
+    ) -> SevenSegment<A, B, C, D, E, F, G, DynamicPolarity>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
+        SevenSegment::new(self, NoPin, polarity)
+    }
 
     /// Shorthand for `with_common::<Cathode>()`.
     ///
     /// This prevents you from having to import `Cathode` or write
     /// `with_common::<seven_segment::Cathode>()`
-    pub fn with_common_cathode(self) -> SevenSegment<A, B, C, D, E, F, G, Cathode> {
+    #[must_use]
+    pub fn with_common_cathode(self) -> SevenSegment<A, B, C, D, E, F, G, Cathode>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
         self.with_common::<Cathode>()
     }
 
@@ -118,19 +638,139 @@ impl<A, B, C, D, E, F, G> SevenSegmentPins<A, B, C, D, E, F, G> {
     ///
     /// This prevents you from having to import `Anode` or write
     /// `with_common::<seven_segment::Anode>()`
-    pub fn with_common_anode(self) -> SevenSegment<A, B, C, D, E, F, G, Anode> {
+    #[must_use]
+    pub fn with_common_anode(self) -> SevenSegment<A, B, C, D, E, F, G, Anode>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
+        self.with_common::<Anode>()
+    }
+
+    /// Constructs `SevenSegment` whose segments are lit by driving their pins high.
+    ///
+    /// This is the same as `with_common_cathode`, named after what the pins do rather than how
+    /// the display is built:
+    ///
+    /// | pin level | segment |
+    /// |-----------|---------|
+    /// | high      | lit     |
+    /// | low       | unlit   |
+    ///
+    /// If the common electrode is switched through `with_common_pin` it's enabled by driving it
+    /// low, use `with_common::<CommonCathodeActiveHigh>()` if it's inverted by a transistor.
+    #[must_use]
+    pub fn with_active_high_segments(self) -> SevenSegment<A, B, C, D, E, F, G, Cathode>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
+        self.with_common::<Cathode>()
+    }
+
+    /// Constructs `SevenSegment` whose segments are lit by driving their pins low.
+    ///
+    /// This is the same as `with_common_anode`, named after what the pins do rather than how
+    /// the display is built:
+    ///
+    /// | pin level | segment |
+    /// |-----------|---------|
+    /// | high      | unlit   |
+    /// | low       | lit     |
+    ///
+    /// If the common electrode is switched through `with_common_pin` it's enabled by driving it
+    /// high, use `with_common::<CommonAnodeActiveLow>()` if it's inverted by a transistor.
+    #[must_use]
+    pub fn with_active_low_segments(self) -> SevenSegment<A, B, C, D, E, F, G, Anode>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
         self.with_common::<Anode>()
     }
 }
 
-/// Represents 7-segment display.
+impl<T> SevenSegmentPins<T, T, T, T, T, T, T> {
+    /// Constructs the pins from an array in `a` to `g` order.
+    ///
+    /// This is convenient if the HAL provides the pins as an array of erased pins.
+    pub fn from_array(pins: [T; 7]) -> Self {
+        let [a, b, c, d, e, f, g] = pins;
+        SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+    }
+}
+
+/// Builder of `SevenSegmentPins` checking all pins were provided
 ///
-/// This struct provides you a method to show a value on the 7-segment display by setting the
-/// appropriate pins high or low.
+/// This has one method per segment and `build` can only be called once all of them were called.
+/// Calling the same method twice or forgetting one is a compile error. Note that it's not
+/// possible to check if the same pin is used multiple times when using references to pins so you
+/// still have to be careful in such case.
+///
+/// ```
+/// # use seven_segment::{OutputPin, SevenSegmentBuilder, Segments};
+/// # struct Pin;
+/// # impl OutputPin for Pin {
+/// #     type Error = core::convert::Infallible;
+/// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// let mut display = SevenSegmentBuilder::new()
+///     .a(Pin)
+///     .b(Pin)
+///     .c(Pin)
+///     .d(Pin)
+///     .e(Pin)
+///     .f(Pin)
+///     .g(Pin)
+///     .build()
+///     .with_common_cathode();
+/// display.set(4).unwrap();
+/// ```
+///
+/// Forgetting a pin doesn't compile:
 ///
-/// Use `SevenSegmentPins` to construct it.
-pub struct SevenSegment<A, B, C, D, E, F, G, Common> {
-    common: core::marker::PhantomData<Common>,
+/// ```compile_fail
+/// # use seven_segment::{OutputPin, SevenSegmentBuilder, Segments};
+/// # struct Pin;
+/// # impl OutputPin for Pin {
+/// #     type Error = core::convert::Infallible;
+/// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+/// # }
+/// let pins = SevenSegmentBuilder::new()
+///     .a(Pin)
+///     .b(Pin)
+///     .c(Pin)
+///     .d(Pin)
+///     .e(Pin)
+///     .f(Pin)
+///     .build();
+/// ```
+pub struct SevenSegmentBuilder<A, B, C, D, E, F, G> {
     a: A,
     b: B,
     c: C,
@@ -140,263 +780,4799 @@ pub struct SevenSegment<A, B, C, D, E, F, G, Common> {
     g: G,
 }
 
-impl<A, B, C, D, E, F, G, Common> SevenSegment<A, B, C, D, E, F, G, Common>
-where
-    A: OutputPin,
-    B: OutputPin<Error = A::Error>,
-    C: OutputPin<Error = A::Error>,
-    D: OutputPin<Error = A::Error>,
-    E: OutputPin<Error = A::Error>,
-    F: OutputPin<Error = A::Error>,
-    G: OutputPin<Error = A::Error>,
-    Common: Polarity,
-{
-    /// Sets the value of the display.
-    ///
-    /// The valid values are 0-9. In case of invalid value, the display will be blank.
-    pub fn set(&mut self, value: u8) -> Result<(), A::Error> {
-        let mask = match value {
-            //      a  b  c  d  e  f  g
-            0x0 => (1, 1, 1, 1, 1, 1, 0),
-            0x1 => (0, 1, 1, 0, 0, 0, 0),
-            0x2 => (1, 1, 0, 1, 1, 0, 1),
-            0x3 => (1, 1, 1, 1, 0, 0, 1),
-            0x4 => (0, 1, 1, 0, 0, 1, 1),
-            0x5 => (1, 0, 1, 1, 0, 1, 1),
-            0x6 => (1, 0, 1, 1, 1, 1, 1),
-            0x7 => (1, 1, 1, 0, 0, 0, 0),
-            0x8 => (1, 1, 1, 1, 1, 1, 1),
-            0x9 => (1, 1, 1, 1, 0, 1, 1),
-            0xa => (1, 1, 1, 0, 1, 1, 1),
-            0xb => (0, 0, 1, 1, 1, 1, 1),
-            0xc => (1, 0, 0, 1, 1, 1, 0),
-            0xd => (0, 1, 1, 1, 1, 0, 1),
-            0xe => (1, 0, 0, 1, 1, 1, 1),
-            0xf => (1, 0, 0, 0, 1, 1, 1),
-            _ => (0, 0, 0, 0, 0, 0, 0),
-        };
-
-        if mask.0 == Common::is_cathode() as u8 {
-            self.a.set_high()?;
-        } else {
-            self.a.set_low()?;
+impl SevenSegmentBuilder<NoPin, NoPin, NoPin, NoPin, NoPin, NoPin, NoPin> {
+    /// Creates the builder with no pins set.
+    pub fn new() -> Self {
+        SevenSegmentBuilder {
+            a: NoPin,
+            b: NoPin,
+            c: NoPin,
+            d: NoPin,
+            e: NoPin,
+            f: NoPin,
+            g: NoPin,
         }
+    }
+}
 
-        if mask.1 == Common::is_cathode() as u8 {
-            self.b.set_high()?;
-        } else {
-            self.b.set_low()?;
-        }
+impl Default for SevenSegmentBuilder<NoPin, NoPin, NoPin, NoPin, NoPin, NoPin, NoPin> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        if mask.2 == Common::is_cathode() as u8 {
-            self.c.set_high()?;
-        } else {
-            self.c.set_low()?;
+impl<B, C, D, E, F, G> SevenSegmentBuilder<NoPin, B, C, D, E, F, G> {
+    /// Sets the pin driving the upper horizontal bar.
+    pub fn a<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<P, B, C, D, E, F, G> {
+        SevenSegmentBuilder {
+            a: pin,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
         }
+    }
+}
 
-        if mask.3 == Common::is_cathode() as u8 {
-            self.d.set_high()?;
-        } else {
-            self.d.set_low()?;
+impl<A, C, D, E, F, G> SevenSegmentBuilder<A, NoPin, C, D, E, F, G> {
+    /// Sets the pin driving the upper right vertical bar.
+    pub fn b<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<A, P, C, D, E, F, G> {
+        SevenSegmentBuilder {
+            a: self.a,
+            b: pin,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
         }
+    }
+}
 
-        if mask.4 == Common::is_cathode() as u8 {
-            self.e.set_high()?;
-        } else {
-            self.e.set_low()?;
+impl<A, B, D, E, F, G> SevenSegmentBuilder<A, B, NoPin, D, E, F, G> {
+    /// Sets the pin driving the lower right vertical bar.
+    pub fn c<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<A, B, P, D, E, F, G> {
+        SevenSegmentBuilder {
+            a: self.a,
+            b: self.b,
+            c: pin,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
         }
+    }
+}
 
-        if mask.5 == Common::is_cathode() as u8 {
-            self.f.set_high()?;
-        } else {
-            self.f.set_low()?;
+impl<A, B, C, E, F, G> SevenSegmentBuilder<A, B, C, NoPin, E, F, G> {
+    /// Sets the pin driving the lower horizontal bar.
+    pub fn d<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<A, B, C, P, E, F, G> {
+        SevenSegmentBuilder {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: pin,
+            e: self.e,
+            f: self.f,
+            g: self.g,
         }
+    }
+}
 
-        if mask.6 == Common::is_cathode() as u8 {
-            self.g.set_high()?;
-        } else {
-            self.g.set_low()?;
+impl<A, B, C, D, F, G> SevenSegmentBuilder<A, B, C, D, NoPin, F, G> {
+    /// Sets the pin driving the lower left vertical bar.
+    pub fn e<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<A, B, C, D, P, F, G> {
+        SevenSegmentBuilder {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: pin,
+            f: self.f,
+            g: self.g,
         }
-        Ok(())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    fn test_digit(digit: u8, expected: (u8, u8, u8, u8, u8, u8, u8)) {
-        struct TestPin(u8);
-
-        impl TestPin {
-            fn inv(&self) -> u8 {
-                if self.0 == 0 {
-                    1
-                // Keep values > 1 in order to detect bugs
-                } else if self.0 == 1 {
-                    0
-                } else {
-                    self.0
-                }
-            }
+impl<A, B, C, D, E, G> SevenSegmentBuilder<A, B, C, D, E, NoPin, G> {
+    /// Sets the pin driving the upper left vertical bar.
+    pub fn f<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<A, B, C, D, E, P, G> {
+        SevenSegmentBuilder {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: pin,
+            g: self.g,
         }
+    }
+}
 
-        impl super::OutputPin for &'_ mut TestPin {
-            type Error = core::convert::Infallible;
-
-            fn set_high(&mut self) -> Result<(), Self::Error> {
-                (*self).0 = 1;
-                Ok(())
+impl<A, B, C, D, E, F> SevenSegmentBuilder<A, B, C, D, E, F, NoPin> {
+    /// Sets the pin driving the middle horizontal bar.
+    pub fn g<P: OutputPin>(self, pin: P) -> SevenSegmentBuilder<A, B, C, D, E, F, P> {
+        SevenSegmentBuilder {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: pin,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G> SevenSegmentBuilder<A, B, C, D, E, F, G>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+{
+    /// Returns the pins.
+    pub fn build(self) -> SevenSegmentPins<A, B, C, D, E, F, G> {
+        SevenSegmentPins {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+        }
+    }
+}
+
+/// States of individual segments
+///
+/// `true` means the segment is lit, regardless of polarity. See `SevenSegmentPins` for the
+/// diagram showing which segment is which.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Segments {
+    /// Upper horizontal bar
+    pub a: bool,
+    /// Upper right vertical bar
+    pub b: bool,
+    /// Lower right vertical bar
+    pub c: bool,
+    /// Lower horizontal bar
+    pub d: bool,
+    /// Lower left vertical bar
+    pub e: bool,
+    /// Upper left vertical bar
+    pub f: bool,
+    /// Middle horizontal bar
+    pub g: bool,
+}
+
+impl Segments {
+    /// Converts the mask used in glyph tables.
+    const fn from_mask(mask: (u8, u8, u8, u8, u8, u8, u8)) -> Self {
+        Segments {
+            a: mask.0 == 1,
+            b: mask.1 == 1,
+            c: mask.2 == 1,
+            d: mask.3 == 1,
+            e: mask.4 == 1,
+            f: mask.5 == 1,
+            g: mask.6 == 1,
+        }
+    }
+
+    /// Only the upper horizontal bar is lit.
+    pub const A: Segments = Segments::from_bits(0x01);
+    /// Only the upper right vertical bar is lit.
+    pub const B: Segments = Segments::from_bits(0x02);
+    /// Only the lower right vertical bar is lit.
+    pub const C: Segments = Segments::from_bits(0x04);
+    /// Only the lower horizontal bar is lit.
+    pub const D: Segments = Segments::from_bits(0x08);
+    /// Only the lower left vertical bar is lit.
+    pub const E: Segments = Segments::from_bits(0x10);
+    /// Only the upper left vertical bar is lit.
+    pub const F: Segments = Segments::from_bits(0x20);
+    /// Only the middle horizontal bar is lit.
+    pub const G: Segments = Segments::from_bits(0x40);
+
+    /// Minus sign, only the middle segment is lit.
+    pub const MINUS: Segments = Segments::from_mask((0, 0, 0, 0, 0, 0, 1));
+
+    /// Error indication, the letter `E`.
+    pub const ERROR: Segments = Segments::from_mask((1, 0, 0, 1, 1, 1, 1));
+
+    /// Overflow indication, all horizontal bars are lit.
+    pub const OVERFLOW: Segments = Segments::from_mask((1, 0, 0, 1, 0, 0, 1));
+
+    /// Degree sign, the small circle in the upper half.
+    ///
+    /// Show it followed by `C` or `F` on the next digit for temperatures.
+    pub const DEGREE: Segments = Segments::from_mask((1, 1, 0, 0, 0, 1, 1));
+
+    /// Segments used to show hexadecimal digits, indexed by the digit.
+    ///
+    /// This is the table used by `set` and `set_hex` unless it's overridden using `with_glyphs`,
+    /// so it can be referenced when verifying code using the driver. `SevenSegment::glyphs`
+    /// returns the table used by a particular display.
+    pub const HEX_DIGITS: [Segments; 16] = [
+        //                   a  b  c  d  e  f  g
+        Segments::from_mask((1, 1, 1, 1, 1, 1, 0)),
+        Segments::from_mask((0, 1, 1, 0, 0, 0, 0)),
+        Segments::from_mask((1, 1, 0, 1, 1, 0, 1)),
+        Segments::from_mask((1, 1, 1, 1, 0, 0, 1)),
+        Segments::from_mask((0, 1, 1, 0, 0, 1, 1)),
+        Segments::from_mask((1, 0, 1, 1, 0, 1, 1)),
+        Segments::from_mask((1, 0, 1, 1, 1, 1, 1)),
+        Segments::from_mask((1, 1, 1, 0, 0, 0, 0)),
+        Segments::from_mask((1, 1, 1, 1, 1, 1, 1)),
+        Segments::from_mask((1, 1, 1, 1, 0, 1, 1)),
+        Segments::from_mask((1, 1, 1, 0, 1, 1, 1)),
+        Segments::from_mask((0, 0, 1, 1, 1, 1, 1)),
+        Segments::from_mask((1, 0, 0, 1, 1, 1, 0)),
+        Segments::from_mask((0, 1, 1, 1, 1, 0, 1)),
+        Segments::from_mask((1, 0, 0, 1, 1, 1, 1)),
+        Segments::from_mask((1, 0, 0, 0, 1, 1, 1)),
+    ];
+
+    /// Segments used to show decimal digits, indexed by the digit.
+    pub const DIGITS: [Segments; 10] = {
+        let mut digits = [Segments::from_mask((0, 0, 0, 0, 0, 0, 0)); 10];
+        let mut i = 0;
+        while i < digits.len() {
+            digits[i] = Segments::HEX_DIGITS[i];
+            i += 1;
+        }
+        digits
+    };
+
+    /// Returns the first `count` segments in the order `a` to `g`, e.g. for a bar graph.
+    ///
+    /// The outer segments are filled clockwise starting from the top one and the middle segment
+    /// is the last one. Counts above 7 light all segments.
+    pub const fn bar(count: u8) -> Self {
+        if count >= 7 {
+            Segments::from_bits(0x7f)
+        } else {
+            Segments::from_bits((1 << count) - 1)
+        }
+    }
+
+    /// Returns the segments needed to show the decimal digit.
+    ///
+    /// Returns `None` for values above 9.
+    pub const fn from_digit(digit: u8) -> Option<Self> {
+        if digit < 10 {
+            Some(Segments::DIGITS[digit as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the segments needed to show the hexadecimal digit.
+    ///
+    /// Returns `None` for values above 15.
+    pub const fn from_hex_digit(digit: u8) -> Option<Self> {
+        if digit < 16 {
+            Some(Segments::HEX_DIGITS[digit as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Converts bit mask to segments.
+    ///
+    /// Bit 0 (the least significant) is segment `a`, bit 1 is `b` and so on up to bit 6 being
+    /// segment `g`. Bit 7 is ignored.
+    pub const fn from_bits(bits: u8) -> Self {
+        Segments {
+            a: bits & 0x01 != 0,
+            b: bits & 0x02 != 0,
+            c: bits & 0x04 != 0,
+            d: bits & 0x08 != 0,
+            e: bits & 0x10 != 0,
+            f: bits & 0x20 != 0,
+            g: bits & 0x40 != 0,
+        }
+    }
+
+    /// Converts segments to bit mask.
+    ///
+    /// The order of bits is the same as in `from_bits`, bit 7 is always zero.
+    pub const fn bits(self) -> u8 {
+        self.a as u8
+            | (self.b as u8) << 1
+            | (self.c as u8) << 2
+            | (self.d as u8) << 3
+            | (self.e as u8) << 4
+            | (self.f as u8) << 5
+            | (self.g as u8) << 6
+    }
+
+    /// Returns the segments with all lit segments turned off and vice versa.
+    pub const fn complement(self) -> Self {
+        Segments {
+            a: !self.a,
+            b: !self.b,
+            c: !self.c,
+            d: !self.d,
+            e: !self.e,
+            f: !self.f,
+            g: !self.g,
+        }
+    }
+
+    /// Returns the segments lit in either `self` or `other`, the same as `|`.
+    pub const fn union(self, other: Self) -> Self {
+        Segments::from_bits(self.bits() | other.bits())
+    }
+
+    /// Returns the segments lit in both `self` and `other`, the same as `&`.
+    pub const fn intersection(self, other: Self) -> Self {
+        Segments::from_bits(self.bits() & other.bits())
+    }
+
+    /// Returns the segments needed to show the character.
+    ///
+    /// Digits, space, `-`, `_`, `°` and letters that can be recognizably shown on 7-segment
+    /// display are supported. Letters that have both upper and lower case representation (`C`,
+    /// `H`, `O`, `U`) respect the case, the other letters are shown the same way regardless of
+    /// case.
+    pub const fn from_char(c: char) -> Result<Self, UnsupportedChar> {
+        let mask = match c {
+            //      a  b  c  d  e  f  g
+            '0'..='9' => return Ok(Segments::DIGITS[c as usize - '0' as usize]),
+            'A' | 'a' => (1, 1, 1, 0, 1, 1, 1),
+            'B' | 'b' => (0, 0, 1, 1, 1, 1, 1),
+            'C' => (1, 0, 0, 1, 1, 1, 0),
+            'c' => (0, 0, 0, 1, 1, 0, 1),
+            'D' | 'd' => (0, 1, 1, 1, 1, 0, 1),
+            'E' | 'e' => (1, 0, 0, 1, 1, 1, 1),
+            'F' | 'f' => (1, 0, 0, 0, 1, 1, 1),
+            'G' | 'g' => (1, 0, 1, 1, 1, 1, 0),
+            'H' => (0, 1, 1, 0, 1, 1, 1),
+            'h' => (0, 0, 1, 0, 1, 1, 1),
+            'I' | 'i' => (0, 0, 0, 0, 1, 1, 0),
+            'J' | 'j' => (0, 1, 1, 1, 1, 0, 0),
+            'L' | 'l' => (0, 0, 0, 1, 1, 1, 0),
+            'N' | 'n' => (0, 0, 1, 0, 1, 0, 1),
+            'O' => (1, 1, 1, 1, 1, 1, 0),
+            'o' => (0, 0, 1, 1, 1, 0, 1),
+            'P' | 'p' => (1, 1, 0, 0, 1, 1, 1),
+            'Q' | 'q' => (1, 1, 1, 0, 0, 1, 1),
+            'R' | 'r' => (0, 0, 0, 0, 1, 0, 1),
+            'S' | 's' => (1, 0, 1, 1, 0, 1, 1),
+            'T' | 't' => (0, 0, 0, 1, 1, 1, 1),
+            'U' => (0, 1, 1, 1, 1, 1, 0),
+            'u' => (0, 0, 1, 1, 1, 0, 0),
+            'Y' | 'y' => (0, 1, 1, 1, 0, 1, 1),
+            '-' => return Ok(Segments::MINUS),
+            '°' => return Ok(Segments::DEGREE),
+            '_' => (0, 0, 0, 1, 0, 0, 0),
+            ' ' => (0, 0, 0, 0, 0, 0, 0),
+            _ => return Err(UnsupportedChar(c)),
+        };
+
+        Ok(Segments::from_mask(mask))
+    }
+
+    /// Draws the segments as ASCII art, e.g. for tests and simulators.
+    ///
+    /// The result has five lines of six characters separated by `\n`, unlit segments are spaces.
+    /// This is how `3` looks like:
+    ///
+    /// ```text
+    ///  ____
+    ///      |
+    ///  ____|
+    ///      |
+    ///  ____|
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn render_ascii(self) -> std::string::String {
+        let horizontal = |on| if on { "____" } else { "    " };
+        let vertical = |on| if on { '|' } else { ' ' };
+        let mut out = std::string::String::with_capacity(34);
+        out.push(' ');
+        out.push_str(horizontal(self.a));
+        out.push_str(" \n");
+        for (left, middle, right) in [(self.f, self.g, self.b), (self.e, self.d, self.c)] {
+            out.push(vertical(left));
+            out.push_str("    ");
+            out.push(vertical(right));
+            out.push('\n');
+            out.push(vertical(left));
+            out.push_str(horizontal(middle));
+            out.push(vertical(right));
+            out.push('\n');
+        }
+        out.pop();
+        out
+    }
+}
+
+impl From<u8> for Segments {
+    fn from(bits: u8) -> Self {
+        Segments::from_bits(bits)
+    }
+}
+
+impl From<Segments> for u8 {
+    fn from(segments: Segments) -> Self {
+        segments.bits()
+    }
+}
+
+impl core::ops::BitOr for Segments {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl core::ops::BitOrAssign for Segments {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+impl core::ops::BitAnd for Segments {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl core::ops::BitAndAssign for Segments {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(rhs);
+    }
+}
+
+impl core::ops::BitXor for Segments {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Segments::from_bits(self.bits() ^ rhs.bits())
+    }
+}
+
+impl core::ops::BitXorAssign for Segments {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+impl core::ops::Not for Segments {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+/// Serializes the segments as the bit mask described in `Segments::bits`.
+///
+/// This keeps the custom glyph tables stored in configuration files compact. The tables are just
+/// arrays of `Segments` so they are supported as well.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Segments {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.bits())
+    }
+}
+
+/// Deserializes the segments from the bit mask described in `Segments::from_bits`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Segments {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Segments::from_bits)
+    }
+}
+
+/// Error returned when a character can not be shown on the display.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct UnsupportedChar(pub char);
+
+impl core::fmt::Display for UnsupportedChar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "the character {:?} can not be shown on 7-segment display",
+            self.0
+        )
+    }
+}
+
+/// Pin levels of a digit computed in advance, see `SevenSegment::encode`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EncodedDigit {
+    levels: u8,
+    segments: Segments,
+    value: Option<u8>,
+}
+
+/// Decimal digit, a value in the range 0-9.
+///
+/// Parsing the input into `Digit` validates it once at the boundary, the digit can then be shown
+/// without further checks. Pass it to `SevenSegment::set_value`, which accepts it as well as the
+/// integer types. (`set` keeps taking `u8` so that integer literals still work.)
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Digit(u8);
+
+impl Digit {
+    /// Returns the digit if the value is in the range 0-9.
+    pub const fn new(value: u8) -> Option<Self> {
+        if value <= 9 {
+            Some(Digit(value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of the digit.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl From<Digit> for u8 {
+    fn from(digit: Digit) -> Self {
+        digit.0
+    }
+}
+
+impl core::convert::TryFrom<u8> for Digit {
+    type Error = InvalidDigit;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Digit::new(value).ok_or(InvalidDigit(value))
+    }
+}
+
+impl core::convert::TryFrom<char> for Digit {
+    type Error = UnsupportedChar;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.to_digit(10)
+            .map(|value| Digit(value as u8))
+            .ok_or(UnsupportedChar(c))
+    }
+}
+
+impl core::fmt::Display for Digit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Error returned when a value is not a decimal digit.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidDigit(pub u8);
+
+impl core::fmt::Display for InvalidDigit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} is not a decimal digit", self.0)
+    }
+}
+
+/// Error returned when a byte is not an ASCII digit.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidAsciiDigit(pub u8);
+
+impl core::fmt::Display for InvalidAsciiDigit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:02x} is not an ASCII digit", self.0)
+    }
+}
+
+/// Error returned when there's no auxiliary indicator at the index.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidAuxIndex(pub usize);
+
+impl core::fmt::Display for InvalidAuxIndex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "there's no auxiliary indicator at index {}", self.0)
+    }
+}
+
+/// Error returned by methods that validate the value before showing it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SetError<V, E> {
+    /// The value can not be shown.
+    ///
+    /// The display was not modified in this case.
+    Invalid(V),
+    /// Setting a pin failed.
+    Pin(E),
+}
+
+impl<V: core::fmt::Display, E: core::fmt::Display> core::fmt::Display for SetError<V, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SetError::Invalid(error) => core::fmt::Display::fmt(error, f),
+            SetError::Pin(error) => write!(f, "failed to set pin: {}", error),
+        }
+    }
+}
+
+/// Values shown by `set` and the related methods.
+///
+/// Values above the range blank the display. In version 0.2 the hexadecimal digits were shown
+/// already, this allows code which relies on values 10-15 blanking the display to keep working.
+/// `set_hex` always shows hexadecimal digits.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ValueRange {
+    /// Values 0-15 are shown, 10-15 as hexadecimal digits
+    #[default]
+    Hex,
+    /// Values 0-9 are shown, the display is blank for values above 9
+    Decimal,
+}
+
+/// Orientation in which the display is mounted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Rotation {
+    /// The display is mounted as intended
+    #[default]
+    Normal,
+    /// The display is mounted upside down
+    ///
+    /// This swaps the `a` and `d`, `b` and `e`, `c` and `f` segments, `g` stays in place.
+    Flip180,
+}
+
+impl Rotation {
+    /// Returns which segments need to be lit on the rotated display to show `segments`.
+    pub const fn apply(self, segments: Segments) -> Segments {
+        match self {
+            Rotation::Normal => segments,
+            Rotation::Flip180 => Segments {
+                a: segments.d,
+                b: segments.e,
+                c: segments.f,
+                d: segments.a,
+                e: segments.b,
+                f: segments.c,
+                g: segments.g,
+            },
+        }
+    }
+
+    /// Returns the pin driving the segment on the rotated display.
+    fn segment(self, segment: Segment) -> Segment {
+        match (self, segment) {
+            (Rotation::Normal, segment) => segment,
+            (Rotation::Flip180, Segment::A) => Segment::D,
+            (Rotation::Flip180, Segment::B) => Segment::E,
+            (Rotation::Flip180, Segment::C) => Segment::F,
+            (Rotation::Flip180, Segment::D) => Segment::A,
+            (Rotation::Flip180, Segment::E) => Segment::B,
+            (Rotation::Flip180, Segment::F) => Segment::C,
+            (Rotation::Flip180, segment) => segment,
+        }
+    }
+}
+
+/// Case of the hexadecimal digits, see `SevenSegment::with_hex_case`.
+///
+/// `b` and `d` are always lowercase since uppercase `B` and `D` would look like `8` and `0`.
+/// `A`, `E` and `F` are always uppercase, only `C` has both forms.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HexCase {
+    /// Uppercase where possible: `A b C d E F`
+    #[default]
+    Upper,
+    /// Lowercase where possible: `A b c d E F`
+    Lower,
+}
+
+impl HexCase {
+    /// Returns the segments showing the hexadecimal digit `C` in this case.
+    pub const fn c(self) -> Segments {
+        match self {
+            HexCase::Upper => Segments::from_mask((1, 0, 0, 1, 1, 1, 0)),
+            HexCase::Lower => Segments::from_mask((0, 0, 0, 1, 1, 0, 1)),
+        }
+    }
+}
+
+/// Style of the digits 6, 7 and 9, see `SevenSegment::with_digit_style`.
+///
+/// By default 6 and 9 are shown with the tails and 7 without it, which matches most displays
+/// in the wild. The styles make the three digits consistent.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DigitStyle {
+    /// 6 without the top segment, 9 without the bottom segment and 7 using three segments
+    Classic,
+    /// 6 with the top segment, 9 with the bottom segment and 7 with the top left segment
+    FullTail,
+}
+
+impl DigitStyle {
+    /// Returns the table of hexadecimal digits using this style.
+    pub const fn glyphs(self) -> [Segments; 16] {
+        let mut glyphs = Segments::HEX_DIGITS;
+        match self {
+            DigitStyle::Classic => {
+                glyphs[6].a = false;
+                glyphs[9].d = false;
+            }
+            DigitStyle::FullTail => glyphs[7].f = true,
+        }
+        glyphs
+    }
+}
+
+/// Mirroring of the display, e.g. when it's viewed through a mirror.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mirror {
+    /// The display is viewed directly
+    #[default]
+    None,
+    /// The display is mirrored left to right
+    ///
+    /// This swaps the `b` and `f`, `c` and `e` segments, `a`, `d` and `g` stay in place.
+    Horizontal,
+}
+
+impl Mirror {
+    /// Returns which segments need to be lit on the mirrored display to show `segments`.
+    pub const fn apply(self, segments: Segments) -> Segments {
+        match self {
+            Mirror::None => segments,
+            Mirror::Horizontal => Segments {
+                a: segments.a,
+                b: segments.f,
+                c: segments.e,
+                d: segments.d,
+                e: segments.c,
+                f: segments.b,
+                g: segments.g,
+            },
+        }
+    }
+
+    /// Returns the pin driving the segment on the mirrored display.
+    fn segment(self, segment: Segment) -> Segment {
+        match (self, segment) {
+            (Mirror::None, segment) => segment,
+            (Mirror::Horizontal, Segment::B) => Segment::F,
+            (Mirror::Horizontal, Segment::C) => Segment::E,
+            (Mirror::Horizontal, Segment::E) => Segment::C,
+            (Mirror::Horizontal, Segment::F) => Segment::B,
+            (Mirror::Horizontal, segment) => segment,
+        }
+    }
+}
+
+/// Identifies the pin of the display.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Segment {
+    /// Top segment
+    A,
+    /// Top right segment
+    B,
+    /// Bottom right segment
+    C,
+    /// Bottom segment
+    D,
+    /// Bottom left segment
+    E,
+    /// Top left segment
+    F,
+    /// Middle segment
+    G,
+    /// Decimal point
+    Dp,
+    /// The common electrode attached using `with_common_pin` or driven by `MultiplexedDisplay`
+    Common,
+}
+
+impl core::fmt::Display for Segment {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Segment::A => "segment a",
+            Segment::B => "segment b",
+            Segment::C => "segment c",
+            Segment::D => "segment d",
+            Segment::E => "segment e",
+            Segment::F => "segment f",
+            Segment::G => "segment g",
+            Segment::Dp => "decimal point",
+            Segment::Common => "common",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Error returned when setting a pin fails.
+///
+/// Apart from the error returned by the HAL it tells you which pin failed so you know which
+/// trace or solder joint to check. The pins that were set before the failing one keep their new
+/// state, the remaining ones weren't touched.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PinError<E> {
+    /// The pin that failed
+    pub segment: Segment,
+    /// The error returned by the pin
+    pub source: E,
+}
+
+impl<E> PinError<E> {
+    /// Returns a function to be passed to `map_err`.
+    pub(crate) fn at(segment: Segment) -> impl FnOnce(E) -> Self {
+        move |source| PinError { segment, source }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for PinError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.segment, self.source)
+    }
+}
+
+/// Receives the segments shown on the display, see `SevenSegment::with_observer`.
+///
+/// This is implemented for `()`, which ignores them, and for closures.
+pub trait SegmentObserver {
+    /// Called with the segments that are being shown.
+    fn observe(&mut self, segments: Segments);
+}
+
+impl SegmentObserver for () {
+    #[inline]
+    fn observe(&mut self, _: Segments) {}
+}
+
+impl<F: FnMut(Segments)> SegmentObserver for F {
+    fn observe(&mut self, segments: Segments) {
+        self(segments)
+    }
+}
+
+/// Display which can show a single digit or arbitrary segments.
+///
+/// This is implemented by `SevenSegment`, `port::PortSevenSegment` (including shift registers) and
+/// `multiplex::MultiplexedDisplay`, so code such as `blink::Blink` can work with any of them.
+/// The trait is object safe, use `dyn SegmentSink<Error = E>` if you need to choose the backend
+/// at runtime. Backends with different error types can be stored together after converting the
+/// errors using `map_err`:
+///
+/// ```
+/// use seven_segment::SegmentSink;
+///
+/// fn show_all(displays: &mut [&mut dyn SegmentSink<Error = ()>], value: u8) -> Result<(), ()> {
+///     for display in displays {
+///         display.set_digit(value)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub trait SegmentSink {
+    /// Error returned when the display can not be updated
+    type Error;
+
+    /// Shows the digit, the same way `SevenSegment::set` does.
+    fn set_digit(&mut self, value: u8) -> Result<(), Self::Error>;
+
+    /// Lights exactly the given segments.
+    fn set_segments(&mut self, segments: Segments) -> Result<(), Self::Error>;
+
+    /// Turns off all segments.
+    fn blank(&mut self) -> Result<(), Self::Error> {
+        self.set_segments(Segments::default())
+    }
+
+    /// Returns the display converting its errors using the function.
+    fn map_err<E, Fun: FnMut(Self::Error) -> E>(self, f: Fun) -> MapErr<Self, Fun>
+    where
+        Self: Sized,
+    {
+        MapErr { sink: self, f }
+    }
+
+    /// Borrows the display returning a guard which blanks it when dropped.
+    ///
+    /// The guard dereferences to the display so it can be used to show something only until the
+    /// end of the scope. Use `BlankOnDrop::new` for trait objects.
+    fn blank_on_drop(&mut self) -> BlankOnDrop<'_, Self>
+    where
+        Self: Sized,
+    {
+        BlankOnDrop(self)
+    }
+}
+
+/// Guard blanking the display when dropped, returned by `SegmentSink::blank_on_drop`.
+///
+/// `Drop` can't return errors so if blanking fails the error is ignored. Call `blank` explicitly
+/// before the guard is dropped if you need to handle it.
+pub struct BlankOnDrop<'a, D: SegmentSink + ?Sized>(&'a mut D);
+
+impl<'a, D: SegmentSink + ?Sized> BlankOnDrop<'a, D> {
+    /// Creates the guard borrowing the display.
+    pub fn new(display: &'a mut D) -> Self {
+        BlankOnDrop(display)
+    }
+}
+
+impl<D: SegmentSink + ?Sized> core::ops::Deref for BlankOnDrop<'_, D> {
+    type Target = D;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<D: SegmentSink + ?Sized> core::ops::DerefMut for BlankOnDrop<'_, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+impl<D: SegmentSink + ?Sized> Drop for BlankOnDrop<'_, D> {
+    fn drop(&mut self) {
+        let _ = self.0.blank();
+    }
+}
+
+/// Display converting its errors, returned by `SegmentSink::map_err`.
+pub struct MapErr<S, Fun> {
+    sink: S,
+    f: Fun,
+}
+
+impl<S, Fun> MapErr<S, Fun> {
+    /// Returns the wrapped display.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S: SegmentSink, E, Fun: FnMut(S::Error) -> E> SegmentSink for MapErr<S, Fun> {
+    type Error = E;
+
+    fn set_digit(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.sink.set_digit(value).map_err(&mut self.f)
+    }
+
+    fn set_segments(&mut self, segments: Segments) -> Result<(), Self::Error> {
+        self.sink.set_segments(segments).map_err(&mut self.f)
+    }
+
+    fn blank(&mut self) -> Result<(), Self::Error> {
+        self.sink.blank().map_err(&mut self.f)
+    }
+}
+
+impl<S: SegmentSink + ?Sized> SegmentSink for &mut S {
+    type Error = S::Error;
+
+    fn set_digit(&mut self, value: u8) -> Result<(), Self::Error> {
+        (**self).set_digit(value)
+    }
+
+    fn set_segments(&mut self, segments: Segments) -> Result<(), Self::Error> {
+        (**self).set_segments(segments)
+    }
+
+    fn blank(&mut self) -> Result<(), Self::Error> {
+        (**self).blank()
+    }
+}
+
+/// Adapter for showing formatted text using `core::fmt::Write`
+///
+/// Obtained by calling `writer()` on the display. Each written character is shown using
+/// `set_char`, so a single digit display ends up showing the last character. Writing an
+/// unsupported character or failing to set a pin results in `core::fmt::Error`.
+pub struct Writer<'a, D>(&'a mut D);
+
+/// Drives the pin to the level that makes the segment lit or unlit.
+#[inline]
+fn write_segment<P: OutputPin>(pin: &mut P, on: bool, cathode: bool) -> Result<(), P::Error> {
+    if on == cathode {
+        pin.set_high()
+    } else {
+        pin.set_low()
+    }
+}
+
+/// Drives the common pin to the level that enables or disables the digit.
+pub(crate) fn write_common<P: OutputPin>(
+    pin: &mut P,
+    enabled: bool,
+    active_low: bool,
+) -> Result<(), P::Error> {
+    if enabled == active_low {
+        pin.set_low()
+    } else {
+        pin.set_high()
+    }
+}
+
+/// Pins of the 7-sement display with decimal point
+///
+/// This is the same as `SevenSegmentPins` but it also carries the pin driving the decimal point
+/// which is placed in the lower right corner of the display.
+pub struct SevenSegmentPinsWithDp<A, B, C, D, E, F, G, DP> {
+    /// Upper horizontal bar
+    pub a: A,
+    /// Upper right vertical bar
+    pub b: B,
+    /// Lower right vertical bar
+    pub c: C,
+    /// Lower horizontal bar
+    pub d: D,
+    /// Lower left vertical bar
+    pub e: E,
+    /// Upper left vertical bar
+    pub f: F,
+    /// Middle horizontal bar
+    pub g: G,
+    /// Decimal point
+    pub dp: DP,
+}
+
+impl<A, B, C, D, E, F, G, DP> SevenSegmentPinsWithDp<A, B, C, D, E, F, G, DP> {
+    /// Borrows the pins so that a display can be constructed temporarily.
+    ///
+    /// See `SevenSegmentPins::by_ref` for details.
+    #[allow(clippy::type_complexity)]
+    pub fn by_ref(
+        &mut self,
+    ) -> SevenSegmentPinsWithDp<
+        PinRef<'_, A>,
+        PinRef<'_, B>,
+        PinRef<'_, C>,
+        PinRef<'_, D>,
+        PinRef<'_, E>,
+        PinRef<'_, F>,
+        PinRef<'_, G>,
+        PinRef<'_, DP>,
+    > {
+        SevenSegmentPinsWithDp {
+            a: PinRef(&mut self.a),
+            b: PinRef(&mut self.b),
+            c: PinRef(&mut self.c),
+            d: PinRef(&mut self.d),
+            e: PinRef(&mut self.e),
+            f: PinRef(&mut self.f),
+            g: PinRef(&mut self.g),
+            dp: PinRef(&mut self.dp),
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, DP> SevenSegmentPinsWithDp<A, B, C, D, E, F, G, DP> {
+    /// Constructs `SevenSegment` with specified polarity.
+    #[must_use]
+    pub fn with_common<Common: StaticPolarity>(
+        self,
+    ) -> SevenSegment<A, B, C, D, E, F, G, Common, DP>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+        DP: SegmentPin,
+    {
+        self.with_polarity_kind(Common::KIND)
+    }
+
+    /// Constructs `SevenSegment` with polarity chosen at runtime.
+    #[must_use]
+    pub fn with_polarity(
+        self,
+        polarity: PolarityKind,
+    ) -> SevenSegment<A, B, C, D, E, F, G, DynamicPolarity, DP>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+        DP: SegmentPin,
+    {
+        self.with_polarity_kind(polarity)
+    }
+
+    fn with_polarity_kind<Common>(
+        self,
+        polarity: PolarityKind,
+    ) -> SevenSegment<A, B, C, D, E, F, G, Common, DP> {
+        let pins = SevenSegmentPins {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+        };
+
+        SevenSegment::new(pins, self.dp, polarity)
+    }
+
+    /// Shorthand for `with_common::<Cathode>()`.
+    ///
+    /// This prevents you from having to import `Cathode` or write
+    /// `with_common::<seven_segment::Cathode>()`
+    #[must_use]
+    pub fn with_common_cathode(self) -> SevenSegment<A, B, C, D, E, F, G, Cathode, DP>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+        DP: SegmentPin,
+    {
+        self.with_common::<Cathode>()
+    }
+
+    /// Shorthand for `with_common::<Anode>()`.
+    ///
+    /// This prevents you from having to import `Anode` or write
+    /// `with_common::<seven_segment::Anode>()`
+    #[must_use]
+    pub fn with_common_anode(self) -> SevenSegment<A, B, C, D, E, F, G, Anode, DP>
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+        DP: SegmentPin,
+    {
+        self.with_common::<Anode>()
+    }
+}
+
+/// Placeholder for a pin that isn't connected.
+///
+/// This is used as a default type of optional pins, such as the decimal point or the common
+/// pin.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoPin;
+
+/// Borrowed pin, returned by `SevenSegmentPins::by_ref`.
+///
+/// `embedded-hal` doesn't implement `OutputPin` for references so this adapter does.
+#[derive(Debug)]
+pub struct PinRef<'a, P>(pub &'a mut P);
+
+impl<P: OutputPin> OutputPin for PinRef<'_, P> {
+    type Error = P::Error;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_low()
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_high()
+    }
+}
+
+/// Pin which is known to never fail, returned by `SevenSegmentPins::into_infallible`.
+///
+/// Errors returned by the wrapped pin are ignored and `Infallible` is returned instead. The
+/// display methods then return `PinError<Infallible>` so the error branches can be optimized away
+/// and no `expect` is needed. Only use this for pins that really can't fail, e.g. GPIO of the MCU
+/// whose HAL uses `()` as the error type.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct InfalliblePin<P>(pub P);
+
+impl<P: OutputPin> OutputPin for InfalliblePin<P> {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        let _ = self.0.set_low();
+        Ok(())
+    }
+
+    #[inline]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        let _ = self.0.set_high();
+        Ok(())
+    }
+}
+
+/// Represents 7-segment display.
+///
+/// This struct provides you a method to show a value on the 7-segment display by setting the
+/// appropriate pins high or low.
+///
+/// Use `SevenSegmentPins` to construct it. If your display has a decimal point use
+/// `SevenSegmentPinsWithDp` instead. If the common electrode is driven by a pin too, attach it
+/// using `with_common_pin`. To report the shown segments to a simulator use `with_observer`.
+/// Indicators which aren't part of the digit, like the colon of a clock, can be attached using
+/// `with_aux`.
+///
+/// The display is `Send` or `Sync` if all the pins, the observer and the auxiliary indicators
+/// are, the polarity marker doesn't affect it. So it can be shared e.g. as an RTIC resource
+/// whenever the pins can.
+pub struct SevenSegment<
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    Common,
+    DP = NoPin,
+    CommonPin = NoPin,
+    Observer = (),
+    Aux = NoPin,
+> {
+    common: core::marker::PhantomData<fn() -> Common>,
+    a: A,
+    b: B,
+    c: C,
+    d: D,
+    e: E,
+    f: F,
+    g: G,
+    dp: DP,
+    common_pin: CommonPin,
+    observer: Observer,
+    aux: Aux,
+    state: State,
+}
+
+/// Returns which segments `set` lights for the value, in order from `a` to `g`.
+///
+/// This is part of the public contract: the table won't change without a breaking release so
+/// you can use it in conformance tests. Values 0-15 are shown, invalid values return `None`
+/// since the display is blank. Custom glyphs set using `with_glyphs` are not taken into account.
+pub const fn expected_segments(digit: u8) -> Option<[bool; 7]> {
+    match Segments::from_hex_digit(digit) {
+        Some(s) => Some([s.a, s.b, s.c, s.d, s.e, s.f, s.g]),
+        None => None,
+    }
+}
+
+/// Panics if the value would blank the display and the `strict` feature is enabled.
+#[track_caller]
+pub(crate) fn check_value(value: u8, max: u8) {
+    if cfg!(feature = "strict") {
+        assert!(value <= max, "{} can not be shown on the display", value);
+    }
+}
+
+/// Fails to compile if `DIGIT` is not a decimal digit.
+struct AssertDigit<const DIGIT: u8>;
+
+impl<const DIGIT: u8> AssertDigit<DIGIT> {
+    const VALID: () = assert!(DIGIT <= 9, "the value is not a decimal digit");
+}
+
+/// Bit masks of `Segments::HEX_DIGITS`, this is how the glyph table is stored in the display.
+const DEFAULT_GLYPHS: [u8; 16] = {
+    let mut glyphs = [0; 16];
+    let mut i = 0;
+    while i < glyphs.len() {
+        glyphs[i] = Segments::HEX_DIGITS[i].bits();
+        i += 1;
+    }
+    glyphs
+};
+
+/// The state of the display which doesn't depend on the pins.
+struct State {
+    polarity: PolarityKind,
+    rotation: Rotation,
+    mirror: Mirror,
+    range: ValueRange,
+    inverted: Segments,
+    glyphs: [u8; 16],
+    value: Option<u8>,
+    segments: Segments,
+    brightness: u8,
+    pwm_phase: u8,
+    frozen: bool,
+    /// The decimal point requested while the display was frozen.
+    pending_dp: Option<bool>,
+    invalid: Segments,
+}
+
+impl State {
+    const fn new(polarity: PolarityKind) -> Self {
+        State {
+            polarity,
+            rotation: Rotation::Normal,
+            mirror: Mirror::None,
+            range: ValueRange::Hex,
+            inverted: Segments::from_bits(0),
+            glyphs: DEFAULT_GLYPHS,
+            value: None,
+            segments: Segments::from_bits(0),
+            brightness: u8::MAX,
+            pwm_phase: 0,
+            frozen: false,
+            pending_dp: None,
+            invalid: Segments::from_bits(0),
+        }
+    }
+
+    /// Returns which segment pins need to be driven to show `segments`.
+    #[inline]
+    const fn transform(&self, segments: Segments) -> Segments {
+        self.mirror.apply(self.rotation.apply(segments))
+    }
+
+    /// Returns the pin driving the segment.
+    fn pin(&self, segment: Segment) -> Segment {
+        self.mirror.segment(self.rotation.segment(segment))
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common: StaticPolarity> SevenSegment<A, B, C, D, E, F, G, Common> {
+    /// Constructs the display from the pins in `const` context.
+    ///
+    /// This is the same as `SevenSegmentPins { .. }.with_common()` but it can be used to
+    /// initialize a `static`, e.g. inside a mutex. The builder methods can't be `const` because
+    /// moving pins out of a generic struct isn't allowed in `const fn`.
+    ///
+    /// ```
+    /// use seven_segment::{Cathode, SevenSegment};
+    ///
+    /// pub struct Pin;
+    ///
+    /// impl seven_segment::OutputPin for Pin {
+    ///     type Error = core::convert::Infallible;
+    ///
+    ///     fn set_high(&mut self) -> Result<(), Self::Error> {
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn set_low(&mut self) -> Result<(), Self::Error> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// static DISPLAY: seven_segment::erased::SevenSegment<Pin, Cathode> =
+    ///     SevenSegment::from_pins(Pin, Pin, Pin, Pin, Pin, Pin, Pin);
+    /// ```
+    pub const fn from_pins(a: A, b: B, c: C, d: D, e: E, f: F, g: G) -> Self
+    where
+        A: SegmentPin,
+        B: SegmentPin,
+        C: SegmentPin,
+        D: SegmentPin,
+        E: SegmentPin,
+        F: SegmentPin,
+        G: SegmentPin,
+    {
+        SevenSegment {
+            common: core::marker::PhantomData,
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+            dp: NoPin,
+            common_pin: NoPin,
+            observer: (),
+            aux: NoPin,
+            state: State::new(Common::KIND),
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP> SevenSegment<A, B, C, D, E, F, G, Common, DP> {
+    fn new(pins: SevenSegmentPins<A, B, C, D, E, F, G>, dp: DP, polarity: PolarityKind) -> Self {
+        SevenSegment {
+            common: Default::default(),
+            a: pins.a,
+            b: pins.b,
+            c: pins.c,
+            d: pins.d,
+            e: pins.e,
+            f: pins.f,
+            g: pins.g,
+            dp,
+            common_pin: NoPin,
+            observer: (),
+            aux: NoPin,
+            state: State::new(polarity),
+        }
+    }
+
+    /// Attaches the pin driving the common electrode.
+    ///
+    /// The pin is driven according to polarity so that the digit is disabled when the display is
+    /// blank and enabled otherwise. The pin is not touched until the display is set.
+    #[must_use]
+    pub fn with_common_pin<CommonPin>(
+        self,
+        common_pin: CommonPin,
+    ) -> SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin> {
+        SevenSegment {
+            common: self.common,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+            dp: self.dp,
+            common_pin,
+            observer: self.observer,
+            aux: self.aux,
+            state: self.state,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin>
+{
+    /// Installs the observer which is notified about the segments whenever the pins are written.
+    ///
+    /// This is intended for simulators, which can visualize the display without mocking the
+    /// pins. The observer receives the logical state, `true` means lit, and is also called by
+    /// `tick` so that brightness control can be simulated. Closures taking `Segments` can be
+    /// used as observers. Without an observer there's no overhead.
+    #[must_use]
+    pub fn with_observer<Observer: SegmentObserver>(
+        self,
+        observer: Observer,
+    ) -> SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer> {
+        SevenSegment {
+            common: self.common,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+            dp: self.dp,
+            common_pin: self.common_pin,
+            observer,
+            aux: self.aux,
+            state: self.state,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer>
+{
+    /// Attaches auxiliary indicators, such as the colon or the PM dot of a clock.
+    ///
+    /// The indicators are driven according to polarity, just like segments, and only `set_aux`
+    /// touches them. Pass an array of pins, `set_aux` indexes into it.
+    #[must_use]
+    pub fn with_aux<Aux: AuxPins>(
+        self,
+        aux: Aux,
+    ) -> SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux> {
+        SevenSegment {
+            common: self.common,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+            dp: self.dp,
+            common_pin: self.common_pin,
+            observer: self.observer,
+            aux,
+            state: self.state,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+{
+    /// Detaches the pin driving the common electrode.
+    ///
+    /// The pin is left in the state it was in.
+    #[allow(clippy::type_complexity)]
+    pub fn release_common_pin(
+        self,
+    ) -> (
+        SevenSegment<A, B, C, D, E, F, G, Common, DP, NoPin, Observer, Aux>,
+        CommonPin,
+    ) {
+        let display = SevenSegment {
+            common: self.common,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+            dp: self.dp,
+            common_pin: NoPin,
+            observer: self.observer,
+            aux: self.aux,
+            state: self.state,
+        };
+        (display, self.common_pin)
+    }
+
+    /// Sets the orientation in which the display is mounted.
+    ///
+    /// The segments are remapped when writing the pins so that digits and patterns passed to
+    /// `set_segments` show right-side-up. The decimal point can't be moved so it ends up in the
+    /// top left corner of a flipped display.
+    #[must_use]
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.state.rotation = rotation;
+        self
+    }
+
+    /// Mirrors the glyphs for displays viewed through a mirror.
+    ///
+    /// Like the rotation this is applied when writing the pins, so digits and patterns passed to
+    /// `set_segments` read correctly in the mirror. It can be combined with the rotation.
+    #[must_use]
+    pub fn with_mirror(mut self, mirror: Mirror) -> Self {
+        self.state.mirror = mirror;
+        self
+    }
+
+    /// Inverts the levels of the given segment pins.
+    ///
+    /// This is for boards where some segments are wired through an inverting buffer while others
+    /// aren't. The mask applies to the pins, so if the display is rotated it's not remapped.
+    #[must_use]
+    pub fn with_inverted_segments(mut self, inverted: Segments) -> Self {
+        self.state.inverted = inverted;
+        self
+    }
+
+    /// Chooses whether `set` shows values 10-15 as hexadecimal digits or blanks the display.
+    ///
+    /// See `ValueRange` for details.
+    #[must_use]
+    pub fn with_value_range(mut self, range: ValueRange) -> Self {
+        self.state.range = range;
+        self
+    }
+
+    /// Replaces the table of glyphs used to show digits.
+    ///
+    /// The table is indexed by the value passed to `set`, the entries 10-15 are used for
+    /// hexadecimal digits. Start with `Segments::HEX_DIGITS` and change the glyphs you want to
+    /// look different, e.g. to show 6 and 9 with the tail.
+    #[must_use]
+    pub fn with_glyphs(mut self, glyphs: [Segments; 16]) -> Self {
+        for (stored, glyph) in self.state.glyphs.iter_mut().zip(&glyphs) {
+            *stored = glyph.bits();
+        }
+        self
+    }
+
+    /// Shows the digits 6, 7 and 9 in the given style.
+    ///
+    /// This replaces the table of glyphs with `style.glyphs()` so it overrides `with_glyphs` and
+    /// vice versa.
+    #[must_use]
+    pub fn with_digit_style(self, style: DigitStyle) -> Self {
+        self.with_glyphs(style.glyphs())
+    }
+
+    /// Shows the hexadecimal digit 12 as `C` or `c`.
+    ///
+    /// This affects `set_hex` as well as `set` showing values 10-15. Only the glyph of 12 is
+    /// replaced, call this after `with_glyphs` and `with_digit_style` because they replace the
+    /// whole table.
+    #[must_use]
+    pub fn with_hex_case(mut self, case: HexCase) -> Self {
+        self.state.glyphs[0xc] = case.c().bits();
+        self
+    }
+
+    /// Returns the table of glyphs used to show digits.
+    ///
+    /// This is `Segments::HEX_DIGITS` unless it was replaced using `with_glyphs`. The table is
+    /// logical, rotation, mirroring and polarity are applied when writing the pins.
+    pub fn glyphs(&self) -> [Segments; 16] {
+        let mut glyphs = [Segments::default(); 16];
+        for (glyph, bits) in glyphs.iter_mut().zip(&self.state.glyphs) {
+            *glyph = Segments::from_bits(*bits);
+        }
+        glyphs
+    }
+
+    /// Returns the segments showing the value, the invalid glyph for values outside of the range.
+    #[inline]
+    pub(crate) fn glyph(&self, value: u8) -> Segments {
+        if value > self.max_value() {
+            return self.state.invalid;
+        }
+        self.hex_glyph(value)
+    }
+
+    /// Returns the segments `set` shows for the value, panicking if the `strict` feature rejects it.
+    #[track_caller]
+    pub(crate) fn checked_glyph(&self, value: u8) -> Segments {
+        check_value(value, self.max_value());
+        self.glyph(value)
+    }
+
+    /// Returns the segments showing the value as hexadecimal digit regardless of the range.
+    fn hex_glyph(&self, value: u8) -> Segments {
+        self.state
+            .glyphs
+            .get(usize::from(value))
+            .map_or(self.state.invalid, |bits| Segments::from_bits(*bits))
+    }
+
+    /// Returns the highest value `set` shows.
+    pub(crate) fn max_value(&self) -> u8 {
+        match self.state.range {
+            ValueRange::Hex => 0xf,
+            ValueRange::Decimal => 9,
+        }
+    }
+
+    /// Returns the polarity of the display.
+    ///
+    /// This is useful for logging or when the polarity was chosen at runtime. If it's known at
+    /// compile time `StaticPolarity::IS_ANODE` can be used too.
+    pub fn polarity(&self) -> PolarityKind {
+        self.state.polarity
+    }
+
+    /// Returns the value that was last set.
+    ///
+    /// This is the value passed to the last successful call of `set` or similar method, `None`
+    /// if the display is blank or shows something other than a digit.
+    pub fn current(&self) -> Option<u8> {
+        self.state.value
+    }
+
+    /// Returns the segments that were last set.
+    ///
+    /// This is the logical state, `true` means the segment is lit, regardless of polarity. It's
+    /// not affected by brightness control. Compare the segments to check that two displays show
+    /// the same thing, the displays themselves don't implement `PartialEq` because they own pins.
+    ///
+    /// ```
+    /// # use seven_segment::{OutputPin, SevenSegmentPins};
+    /// # struct Pin;
+    /// # impl OutputPin for Pin {
+    /// #     type Error = core::convert::Infallible;
+    /// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+    /// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+    /// # }
+    /// # fn pins() -> SevenSegmentPins<Pin, Pin, Pin, Pin, Pin, Pin, Pin> {
+    /// #     SevenSegmentPins { a: Pin, b: Pin, c: Pin, d: Pin, e: Pin, f: Pin, g: Pin }
+    /// # }
+    /// let mut left = pins().with_common_cathode();
+    /// let mut right = pins().with_common_anode();
+    /// left.set(4).unwrap();
+    /// right.set_char('4').unwrap();
+    /// assert_eq!(left.segments(), right.segments());
+    /// ```
+    pub fn segments(&self) -> Segments {
+        self.state.segments
+    }
+
+    /// Draws the shown segments as ASCII art, see `Segments::render_ascii`.
+    #[cfg(feature = "std")]
+    pub fn render_ascii(&self) -> std::string::String {
+        self.state.segments.render_ascii()
+    }
+}
+
+impl<P, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<P, P, P, P, P, P, P, Common, DP, CommonPin, Observer, Aux>
+{
+    /// Wraps each of the seven segment pins using `f`.
+    ///
+    /// This is handy for inserting an adapter, e.g. one logging the transitions. The closure also
+    /// receives which segment the pin drives. All pins need to be of the same type, if they're
+    /// not, degrade them to a single type using your HAL first. Other pins and the state are kept.
+    pub fn map_pins<Q, Fun: FnMut(Segment, P) -> Q>(
+        self,
+        mut f: Fun,
+    ) -> SevenSegment<Q, Q, Q, Q, Q, Q, Q, Common, DP, CommonPin, Observer, Aux> {
+        SevenSegment {
+            common: self.common,
+            a: f(Segment::A, self.a),
+            b: f(Segment::B, self.b),
+            c: f(Segment::C, self.c),
+            d: f(Segment::D, self.d),
+            e: f(Segment::E, self.e),
+            f: f(Segment::F, self.f),
+            g: f(Segment::G, self.g),
+            dp: self.dp,
+            common_pin: self.common_pin,
+            observer: self.observer,
+            aux: self.aux,
+            state: self.state,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common> SevenSegment<A, B, C, D, E, F, G, Common> {
+    /// Deconstructs the display and returns the pins.
+    ///
+    /// The pins are left in the state they were in.
+    pub fn into_pins(self) -> SevenSegmentPins<A, B, C, D, E, F, G> {
+        SevenSegmentPins {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP: OutputPin> SevenSegment<A, B, C, D, E, F, G, Common, DP> {
+    /// Deconstructs the display and returns the pins.
+    ///
+    /// The pins are left in the state they were in.
+    pub fn into_pins(self) -> SevenSegmentPinsWithDp<A, B, C, D, E, F, G, DP> {
+        SevenSegmentPinsWithDp {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+            dp: self.dp,
+        }
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer: SegmentObserver, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+{
+    /// Notifies the observer and returns the segments the pins need to show.
+    ///
+    /// This is shared by all methods writing the segment pins. It returns `None` if the display
+    /// is frozen and nothing should be written.
+    #[inline]
+    pub(crate) fn begin_write(&mut self, segments: Segments) -> Option<Segments> {
+        if self.state.frozen {
+            return None;
+        }
+        self.observer.observe(segments);
+        Some(self.state.transform(segments))
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    /// Sets the value of the display.
+    ///
+    /// The valid values are 0-9. In case of invalid value, the display will be blank, unless a
+    /// different glyph was chosen using `set_invalid_glyph`.
+    ///
+    /// For historical reasons values 10-15 are shown as hexadecimal digits. Use `set_hex` if you
+    /// intend to show them. If you want them to blank the display instead, e.g. because your code
+    /// relies on `set(10)` blanking, use `with_value_range(ValueRange::Decimal)`.
+    ///
+    /// # Panics
+    ///
+    /// If the `strict` feature is enabled values that would blank the display panic instead. This
+    /// helps catching logic errors during development.
+    #[inline]
+    pub fn set(&mut self, value: u8) -> Result<(), PinError<A::Error>> {
+        check_value(value, self.max_value());
+        self.set_segments(self.glyph(value))?;
+        if value <= self.max_value() {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Sets the value of the display or blanks it if the value is `None`.
+    ///
+    /// `Some` behaves exactly like `set`. This accepts what `current` returns, so a digit saved
+    /// before showing something else can be restored later.
+    pub fn set_opt(&mut self, value: Option<u8>) -> Result<(), PinError<A::Error>> {
+        match value {
+            Some(value) => self.set(value),
+            None => self.blank(),
+        }
+    }
+
+    /// Sets the value of the display from any integer type.
+    ///
+    /// This behaves exactly like `set` but avoids casting indices to `u8`, which could silently
+    /// truncate large values to valid digits. Values that don't fit into `u8` blank the display
+    /// (or panic if the `strict` feature is enabled) just like other invalid values. `Digit` is
+    /// accepted too and it's always shown.
+    pub fn set_value<N: TryInto<u8>>(&mut self, value: N) -> Result<(), PinError<A::Error>> {
+        self.set(value.try_into().unwrap_or(u8::MAX))
+    }
+
+    /// Computes the pin levels needed to show the value so that they can be cached.
+    ///
+    /// The glyph lookup, rotation and polarity are all resolved here, `show` then only writes
+    /// the pins. The encoding is only valid for this display with its current configuration, if
+    /// you change e.g. the polarity, encode the digits again. Invalid values are treated the same
+    /// way as in `set`.
+    pub fn encode(&self, value: u8) -> EncodedDigit {
+        check_value(value, self.max_value());
+        let segments = self.glyph(value);
+        let physical = self.state.transform(segments).bits() ^ self.state.inverted.bits();
+        let levels = if Common::is_cathode(self.state.polarity) {
+            physical
+        } else {
+            !physical & 0x7f
+        };
+        EncodedDigit {
+            levels,
+            segments,
+            value: if value <= self.max_value() {
+                Some(value)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Shows the digit encoded using `encode`.
+    ///
+    /// This has the same effect as calling `set` with the encoded value.
+    pub fn show(&mut self, digit: &EncodedDigit) -> Result<(), PinError<A::Error>> {
+        self.state.value = digit.value;
+        self.state.segments = digit.segments;
+        if self.state.frozen {
+            return Ok(());
+        }
+        self.observer.observe(digit.segments);
+        self.disable_if_blank(digit.segments)?;
+        let high = |bit: u8| digit.levels & 1 << bit != 0;
+        write_segment(&mut self.a, high(0), true).map_err(PinError::at(Segment::A))?;
+        write_segment(&mut self.b, high(1), true).map_err(PinError::at(Segment::B))?;
+        write_segment(&mut self.c, high(2), true).map_err(PinError::at(Segment::C))?;
+        write_segment(&mut self.d, high(3), true).map_err(PinError::at(Segment::D))?;
+        write_segment(&mut self.e, high(4), true).map_err(PinError::at(Segment::E))?;
+        write_segment(&mut self.f, high(5), true).map_err(PinError::at(Segment::F))?;
+        write_segment(&mut self.g, high(6), true).map_err(PinError::at(Segment::G))?;
+        self.enable_if_lit(digit.segments)
+    }
+
+    /// Sets the value of the display if it's a valid digit.
+    ///
+    /// Unlike `set`, this returns an error for values above 9 and leaves the display unchanged.
+    pub fn try_set(&mut self, value: u8) -> Result<(), SetError<InvalidDigit, PinError<A::Error>>> {
+        if value > 9 {
+            return Err(SetError::Invalid(InvalidDigit(value)));
+        }
+        self.set(value).map_err(SetError::Pin)
+    }
+
+    /// Sets the value of the display to a digit known at compile time.
+    ///
+    /// Values above 9 fail to compile. This catches typos in constants before flashing.
+    ///
+    /// ```
+    /// # use seven_segment::{Cathode, OutputPin, SevenSegment};
+    /// # struct Pin;
+    /// # impl OutputPin for Pin {
+    /// #     type Error = core::convert::Infallible;
+    /// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+    /// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+    /// # }
+    /// let mut display: seven_segment::erased::SevenSegment<Pin, Cathode> =
+    ///     SevenSegment::from_pins(Pin, Pin, Pin, Pin, Pin, Pin, Pin);
+    /// display.set_const::<7>().unwrap();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use seven_segment::{Cathode, OutputPin, SevenSegment};
+    /// # struct Pin;
+    /// # impl OutputPin for Pin {
+    /// #     type Error = core::convert::Infallible;
+    /// #     fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) }
+    /// #     fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) }
+    /// # }
+    /// let mut display: seven_segment::erased::SevenSegment<Pin, Cathode> =
+    ///     SevenSegment::from_pins(Pin, Pin, Pin, Pin, Pin, Pin, Pin);
+    /// display.set_const::<12>().unwrap();
+    /// ```
+    pub fn set_const<const DIGIT: u8>(&mut self) -> Result<(), PinError<A::Error>> {
+        let () = AssertDigit::<DIGIT>::VALID;
+        self.set(DIGIT)
+    }
+
+    /// Sets the value of the display to the ASCII digit.
+    ///
+    /// This accepts `b'0'..=b'9'` so that digits received as text can be shown directly. Other
+    /// bytes return an error and leave the display unchanged.
+    pub fn set_ascii(
+        &mut self,
+        c: u8,
+    ) -> Result<(), SetError<InvalidAsciiDigit, PinError<A::Error>>> {
+        if !c.is_ascii_digit() {
+            return Err(SetError::Invalid(InvalidAsciiDigit(c)));
+        }
+        self.set(c - b'0').map_err(SetError::Pin)
+    }
+
+    /// Lights exactly the given segments.
+    ///
+    /// This allows showing arbitrary patterns, not just digits. The polarity is handled for you,
+    /// so `true` always means the segment is lit.
+    #[inline]
+    pub fn set_segments(&mut self, segments: Segments) -> Result<(), PinError<A::Error>> {
+        self.state.value = None;
+        self.state.segments = segments;
+        self.write(segments)
+    }
+
+    /// Lights the segments according to a byte in the `0bPGFEDCBA` format.
+    ///
+    /// This is the format used by popular Arduino libraries so their glyph tables can be used
+    /// as-is. Bit 0 is segment `a`, bit 6 is segment `g` (the same as `Segments::from_bits`) and
+    /// bit 7 is the decimal point, which is ignored if it's not connected. The decimal point is
+    /// written together with the segments, before the digit is enabled.
+    pub fn set_raw_u8(&mut self, bits: u8) -> Result<(), PinError<A::Error>> {
+        let segments = Segments::from_bits(bits);
+        self.state.value = None;
+        self.state.segments = segments;
+        self.write_with_dp(segments, Some(bits & 0x80 != 0))
+    }
+
+    /// Lights the segments as independent indicators.
+    ///
+    /// The items correspond to segments `a` to `g`, `true` means lit. This is the same as
+    /// `set_segments`, it's convenient when the display is used as seven separate LEDs.
+    pub fn set_bits(&mut self, bits: [bool; 7]) -> Result<(), PinError<A::Error>> {
+        let [a, b, c, d, e, f, g] = bits;
+        self.set_segments(Segments {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        })
+    }
+
+    /// Sets the value of the display turning off segments before lighting new ones.
+    ///
+    /// See `set_segments_ordered` for details.
+    pub fn set_ordered(&mut self, value: u8) -> Result<(), PinError<A::Error>> {
+        check_value(value, self.max_value());
+        self.set_segments_ordered(self.glyph(value))?;
+        if value <= self.max_value() {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Lights exactly the given segments, turning off segments before lighting new ones.
+    ///
+    /// The pins are still set one by one but all segments that should be unlit are turned off
+    /// first. Thus during the transition no segment that is not part of either the old or the
+    /// new glyph is lit and no intermediate glyph with extra segments is visible. This reduces
+    /// glitches on slow GPIO at the cost of going through each pin twice.
+    pub fn set_segments_ordered(&mut self, segments: Segments) -> Result<(), PinError<A::Error>> {
+        self.state.value = None;
+        self.state.segments = segments;
+        if self.state.frozen {
+            return Ok(());
+        }
+        self.observer.observe(segments);
+        let segments = self.state.transform(segments);
+        self.disable_if_blank(segments)?;
+        self.write_phase(segments, false)?;
+        self.write_phase(segments, true)?;
+        self.enable_if_lit(segments)
+    }
+
+    /// Sets the value of the display lighting the segments in groups.
+    ///
+    /// See `set_segments_staggered` for details.
+    pub fn set_staggered<Delay: DelayUs<u16>>(
+        &mut self,
+        value: u8,
+        group: usize,
+        delay: &mut Delay,
+        us: u16,
+    ) -> Result<(), PinError<A::Error>> {
+        check_value(value, self.max_value());
+        self.set_segments_staggered(self.glyph(value), group, delay, us)?;
+        if value <= self.max_value() {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Lights exactly the given segments, at most `group` segments at once.
+    ///
+    /// Lighting all segments at the same moment causes a current spike which may brown out a
+    /// weak supply. This turns the unlit segments off first, like `set_segments_ordered`, and then
+    /// lights the remaining segments in groups of `group` waiting `us` microseconds between them.
+    /// Group of 0 is treated as 1. The other methods keep lighting all segments at once.
+    pub fn set_segments_staggered<Delay: DelayUs<u16>>(
+        &mut self,
+        segments: Segments,
+        group: usize,
+        delay: &mut Delay,
+        us: u16,
+    ) -> Result<(), PinError<A::Error>> {
+        self.state.value = None;
+        self.state.segments = segments;
+        if self.state.frozen {
+            return Ok(());
+        }
+        self.observer.observe(segments);
+        let segments = self.state.transform(segments);
+        self.disable_if_blank(segments)?;
+        self.write_phase(segments, false)?;
+        self.enable_if_lit(segments)?;
+
+        let group = group.max(1);
+        let mut pending = 0;
+        let mut count = 0;
+        for bit in 0..7 {
+            let mask = 1 << bit;
+            if segments.bits() & mask == 0 {
+                continue;
+            }
+            if count == group {
+                self.write_phase(Segments::from_bits(pending), true)?;
+                delay.delay_us(us);
+                pending = 0;
+                count = 0;
+            }
+            pending |= mask;
+            count += 1;
+        }
+        self.write_phase(Segments::from_bits(pending), true)
+    }
+
+    /// Drives only the pins of segments which should be in the given state.
+    fn write_phase(&mut self, segments: Segments, on: bool) -> Result<(), PinError<A::Error>> {
+        let cathode = Common::is_cathode(self.state.polarity);
+        let inverted = self.state.inverted;
+        if segments.a == on {
+            write_segment(&mut self.a, on, cathode != inverted.a)
+                .map_err(PinError::at(Segment::A))?;
+        }
+        if segments.b == on {
+            write_segment(&mut self.b, on, cathode != inverted.b)
+                .map_err(PinError::at(Segment::B))?;
+        }
+        if segments.c == on {
+            write_segment(&mut self.c, on, cathode != inverted.c)
+                .map_err(PinError::at(Segment::C))?;
+        }
+        if segments.d == on {
+            write_segment(&mut self.d, on, cathode != inverted.d)
+                .map_err(PinError::at(Segment::D))?;
+        }
+        if segments.e == on {
+            write_segment(&mut self.e, on, cathode != inverted.e)
+                .map_err(PinError::at(Segment::E))?;
+        }
+        if segments.f == on {
+            write_segment(&mut self.f, on, cathode != inverted.f)
+                .map_err(PinError::at(Segment::F))?;
+        }
+        if segments.g == on {
+            write_segment(&mut self.g, on, cathode != inverted.g)
+                .map_err(PinError::at(Segment::G))?;
+        }
+        Ok(())
+    }
+
+    /// Drives the pins without affecting the stored state.
+    ///
+    /// Nothing is written while the display is frozen.
+    #[inline]
+    fn write(&mut self, segments: Segments) -> Result<(), PinError<A::Error>> {
+        self.write_with_dp(segments, None)
+    }
+
+    /// Drives the pins and the decimal point, if `dp` is `Some`, in a single pass.
+    ///
+    /// The decimal point is written right after the segments, before the digit is enabled. While
+    /// the display is frozen nothing is written and the decimal point is remembered for `thaw`.
+    #[inline]
+    fn write_with_dp(
+        &mut self,
+        segments: Segments,
+        dp: Option<bool>,
+    ) -> Result<(), PinError<A::Error>> {
+        let cathode = Common::is_cathode(self.state.polarity);
+        let segments = match self.begin_write(segments) {
+            Some(segments) => segments,
+            None => {
+                if dp.is_some() {
+                    self.state.pending_dp = dp;
+                }
+                return Ok(());
+            }
+        };
+        let inverted = self.state.inverted;
+        self.disable_if_blank(segments)?;
+        write_segment(&mut self.a, segments.a, cathode != inverted.a)
+            .map_err(PinError::at(Segment::A))?;
+        write_segment(&mut self.b, segments.b, cathode != inverted.b)
+            .map_err(PinError::at(Segment::B))?;
+        write_segment(&mut self.c, segments.c, cathode != inverted.c)
+            .map_err(PinError::at(Segment::C))?;
+        write_segment(&mut self.d, segments.d, cathode != inverted.d)
+            .map_err(PinError::at(Segment::D))?;
+        write_segment(&mut self.e, segments.e, cathode != inverted.e)
+            .map_err(PinError::at(Segment::E))?;
+        write_segment(&mut self.f, segments.f, cathode != inverted.f)
+            .map_err(PinError::at(Segment::F))?;
+        write_segment(&mut self.g, segments.g, cathode != inverted.g)
+            .map_err(PinError::at(Segment::G))?;
+        self.write_dp(dp, cathode)?;
+        self.enable_if_lit(segments)
+    }
+
+    /// Lights or turns off the decimal point if `dp` is `Some`.
+    fn write_dp(&mut self, dp: Option<bool>, cathode: bool) -> Result<(), PinError<A::Error>> {
+        match dp {
+            Some(on) => self
+                .dp
+                .write_segment(on, cathode)
+                .map_err(PinError::at(Segment::Dp)),
+            None => Ok(()),
+        }
+    }
+
+    /// Disables the digit before the segments are turned off.
+    fn disable_if_blank(&mut self, segments: Segments) -> Result<(), PinError<A::Error>> {
+        if segments == Segments::default() {
+            let active_low = Common::is_common_active_low(self.state.polarity);
+            self.common_pin
+                .write_common(false, active_low)
+                .map_err(PinError::at(Segment::Common))?;
+        }
+        Ok(())
+    }
+
+    /// Enables the digit after the segments are lit.
+    fn enable_if_lit(&mut self, segments: Segments) -> Result<(), PinError<A::Error>> {
+        if segments != Segments::default() {
+            let active_low = Common::is_common_active_low(self.state.polarity);
+            self.common_pin
+                .write_common(true, active_low)
+                .map_err(PinError::at(Segment::Common))?;
+        }
+        Ok(())
+    }
+
+    /// Stops writing the segment pins until `thaw` is called.
+    ///
+    /// The display keeps showing what it showed before. Methods setting the segments still
+    /// succeed and remember the requested segments, which are then shown by `thaw`. This includes
+    /// the decimal point requested by `set_with_dp` and `set_raw_u8`; `set_dp`, auxiliary
+    /// indicators and the common pin controlled by `set_one` are not affected.
+    pub fn freeze(&mut self) {
+        self.state.frozen = true;
+    }
+
+    /// Resumes writing the pins and shows the segments requested while the display was frozen.
+    pub fn thaw(&mut self) -> Result<(), PinError<A::Error>> {
+        self.state.frozen = false;
+        let dp = self.state.pending_dp.take();
+        self.write_with_dp(self.state.segments, dp)
+    }
+
+    /// Returns `true` if the display is frozen using `freeze`.
+    pub fn is_frozen(&self) -> bool {
+        self.state.frozen
+    }
+
+    /// Sets the segments shown for invalid values instead of blanking the display.
+    ///
+    /// This allows telling "no data", shown using `blank`, from "bad data", e.g. shown as
+    /// `Segments::MINUS`. `current` still returns `None` for invalid values and the `strict`
+    /// feature still panics on them.
+    pub fn set_invalid_glyph(&mut self, segments: Segments) {
+        self.state.invalid = segments;
+    }
+
+    /// Sets the brightness used by `tick`.
+    ///
+    /// 0 means the display is always off, 255 (the default) means it's always on, the
+    /// brightness is linear in between.
+    pub fn set_brightness(&mut self, level: u8) {
+        self.state.brightness = level;
+    }
+
+    /// Advances the software PWM used to control brightness.
+    ///
+    /// Each call turns the segments on or off such that over 255 calls the shown segments are
+    /// lit for `level` calls, as set by `set_brightness`. You must call this at a fixed frequency
+    /// which is high enough to avoid visible flicker - at least 255 times the refresh rate you
+    /// want, so around 25 kHz for 100 Hz. Beware that changing the shown value lights the
+    /// segments immediately.
+    pub fn tick(&mut self) -> Result<(), PinError<A::Error>> {
+        self.state.pwm_phase = if self.state.pwm_phase >= u8::MAX - 1 {
+            0
+        } else {
+            self.state.pwm_phase + 1
+        };
+
+        if self.state.pwm_phase < self.state.brightness {
+            self.write(self.state.segments)
+        } else {
+            self.write(Segments::default())
+        }
+    }
+
+    /// Shows the character on the display.
+    ///
+    /// See `Segments::from_char` for the list of supported characters. If the character is not
+    /// supported the display is left unchanged and an error is returned so that you can fall back
+    /// to something else.
+    pub fn set_char(
+        &mut self,
+        c: char,
+    ) -> Result<(), SetError<UnsupportedChar, PinError<A::Error>>> {
+        let segments = Segments::from_char(c).map_err(SetError::Invalid)?;
+        self.set_segments(segments).map_err(SetError::Pin)
+    }
+
+    /// Lights or turns off a single segment leaving the others as they are.
+    ///
+    /// This is useful for custom indicators, e.g. a colon driven by one of the segments. The
+    /// segment is the logical one, if the display is rotated a different pin is driven.
+    /// `Segment::Dp` and `Segment::Common` drive the decimal point and the common pin
+    /// respectively, they do nothing if the pin is not connected. The common pin enables the digit
+    /// when `on` is `true`.
+    ///
+    /// Like `set_segments`, lighting a segment enables the digit through the common pin and
+    /// turning off the last lit segment disables it.
+    pub fn set_one(&mut self, segment: Segment, on: bool) -> Result<(), PinError<A::Error>> {
+        let cathode = Common::is_cathode(self.state.polarity);
+        let inverted = self.state.inverted;
+        let pin = self.state.pin(segment);
+        let map_err = PinError::at(pin);
+        match pin {
+            Segment::Dp => {
+                self.state.pending_dp = None;
+                return self.dp.write_segment(on, cathode).map_err(map_err);
+            }
+            Segment::Common => {
+                let active_low = Common::is_common_active_low(self.state.polarity);
+                return self
+                    .common_pin
+                    .write_common(on, active_low)
+                    .map_err(map_err);
+            }
+            _ => (),
+        }
+
+        let mask = 1 << segment as u8;
+        let bits = self.state.segments.bits();
+        let bits = if on { bits | mask } else { bits & !mask };
+        let segments = Segments::from_bits(bits);
+        if !self.state.frozen {
+            self.disable_if_blank(segments)?;
+            match pin {
+                Segment::A => write_segment(&mut self.a, on, cathode != inverted.a),
+                Segment::B => write_segment(&mut self.b, on, cathode != inverted.b),
+                Segment::C => write_segment(&mut self.c, on, cathode != inverted.c),
+                Segment::D => write_segment(&mut self.d, on, cathode != inverted.d),
+                Segment::E => write_segment(&mut self.e, on, cathode != inverted.e),
+                Segment::F => write_segment(&mut self.f, on, cathode != inverted.f),
+                Segment::G => write_segment(&mut self.g, on, cathode != inverted.g),
+                Segment::Dp | Segment::Common => Ok(()),
+            }
+            .map_err(map_err)?;
+            self.enable_if_lit(segments)?;
+            self.observer.observe(segments);
+        }
+        self.state.segments = segments;
+        self.state.value = None;
+        Ok(())
+    }
+
+    /// Shows the percentage as a bar graph of lit segments.
+    ///
+    /// 0 means blank, 100 lights all segments. The number of lit segments is rounded down, so
+    /// each segment is lit once the percentage reaches its seventh, see `Segments::bar` for the
+    /// order. Values above 100 are the same as 100.
+    pub fn set_bar(&mut self, percent: u8) -> Result<(), PinError<A::Error>> {
+        let count = u16::from(percent.min(100)) * 7 / 100;
+        self.set_segments(Segments::bar(count as u8))
+    }
+
+    /// Shows the minus sign.
+    ///
+    /// This lights only the middle segment. Use `Segments::MINUS` if you need the segments, e.g.
+    /// for multi-digit displays.
+    pub fn set_minus(&mut self) -> Result<(), PinError<A::Error>> {
+        self.set_segments(Segments::MINUS)
+    }
+
+    /// Shows the letter `E` indicating an error, e.g. a failed sensor.
+    ///
+    /// Use `Segments::ERROR` if you need the segments, e.g. for multi-digit displays.
+    pub fn set_error(&mut self) -> Result<(), PinError<A::Error>> {
+        self.set_segments(Segments::ERROR)
+    }
+
+    /// Shows all three horizontal bars indicating the value is out of range.
+    ///
+    /// Use `Segments::OVERFLOW` if you need the segments, e.g. for multi-digit displays.
+    pub fn set_overflow(&mut self) -> Result<(), PinError<A::Error>> {
+        self.set_segments(Segments::OVERFLOW)
+    }
+
+    /// Shows the next frame of an animation.
+    ///
+    /// Returns `false` without touching the display if there are no more frames. This is
+    /// intended to be called from a timer interrupt, use `Iterator::cycle` to loop the animation.
+    pub fn step<I: Iterator<Item = Segments>>(
+        &mut self,
+        frames: &mut I,
+    ) -> Result<bool, PinError<A::Error>> {
+        match frames.next() {
+            Some(frame) => self.set_segments(frame).map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Plays the animation, waiting `frame_ms` milliseconds after each frame.
+    ///
+    /// This blocks until all frames were shown so it never returns if the iterator is infinite
+    /// (unless there's an error).
+    pub fn play<I, Delay>(
+        &mut self,
+        frames: I,
+        delay: &mut Delay,
+        frame_ms: u16,
+    ) -> Result<(), PinError<A::Error>>
+    where
+        I: IntoIterator<Item = Segments>,
+        Delay: DelayMs<u16>,
+    {
+        for frame in frames {
+            self.set_segments(frame)?;
+            delay.delay_ms(frame_ms);
+        }
+        Ok(())
+    }
+
+    /// Flashes all segments including the decimal point to visually check the display.
+    ///
+    /// Each of the `cycles` lights everything for `ms` milliseconds and then turns everything off
+    /// for `ms` milliseconds, so the display ends up blank. This is intended to be called once at
+    /// startup.
+    pub fn self_test<Delay: DelayMs<u16>>(
+        &mut self,
+        cycles: u32,
+        delay: &mut Delay,
+        ms: u16,
+    ) -> Result<(), PinError<A::Error>> {
+        let cathode = Common::is_cathode(self.state.polarity);
+        for _ in 0..cycles {
+            self.set_all(true)?;
+            self.dp
+                .write_segment(true, cathode)
+                .map_err(PinError::at(Segment::Dp))?;
+            delay.delay_ms(ms);
+            self.initialize()?;
+            delay.delay_ms(ms);
+        }
+        Ok(())
+    }
+
+    /// Counts from 0 to 9, showing each digit for `ms` milliseconds.
+    ///
+    /// This is handy for checking the wiring and the polarity when bringing up a new board. The
+    /// display keeps showing 9 afterwards.
+    pub fn demo_count<Delay: DelayMs<u16>>(
+        &mut self,
+        delay: &mut Delay,
+        ms: u16,
+    ) -> Result<(), PinError<A::Error>> {
+        for value in 0..=9 {
+            self.set(value)?;
+            delay.delay_ms(ms);
+        }
+        Ok(())
+    }
+
+    /// Shows two values one after another on the single digit, e.g. a two-digit number.
+    ///
+    /// Each value is shown for `ms` milliseconds followed by a blank gap of a quarter of that so
+    /// that repeated digits like `11` can be told apart, the display ends up blank. Call this in
+    /// a loop to keep showing the values.
+    ///
+    /// This is experimental and may change. Note that alternating the digits faster than the eye
+    /// can follow doesn't make both readable, unlike on multiplexed displays they are at the same
+    /// position so they would merge into one glyph. Something around 500 ms works well.
+    pub fn show_two<Delay: DelayMs<u16>>(
+        &mut self,
+        first: u8,
+        second: u8,
+        delay: &mut Delay,
+        ms: u16,
+    ) -> Result<(), PinError<A::Error>> {
+        for value in [first, second] {
+            self.set(value)?;
+            delay.delay_ms(ms);
+            self.blank()?;
+            delay.delay_ms(ms / 4);
+        }
+        Ok(())
+    }
+
+    /// Lights or turns off all segments.
+    ///
+    /// The decimal point, if present, is not affected.
+    pub fn set_all(&mut self, on: bool) -> Result<(), PinError<A::Error>> {
+        let mask = on as u8;
+        self.set_segments(Segments::from_mask((
+            mask, mask, mask, mask, mask, mask, mask,
+        )))
+    }
+
+    /// Lights the segments that are currently off and turns off those that are lit.
+    ///
+    /// Together with `set_all` this is useful for finding dead segments. The decimal point, if
+    /// present, is not affected.
+    pub fn invert(&mut self) -> Result<(), PinError<A::Error>> {
+        self.set_segments(self.state.segments.complement())
+    }
+
+    /// Lights the next segment in the test sweep.
+    ///
+    /// The sweep lights exactly one segment at a time going from `a` to `g`, then blanks the
+    /// display and starts again. The position is derived from the shown segments so if they
+    /// don't match any step the sweep starts from `a`. Calling this periodically is handy for
+    /// bring-up and EMC tests.
+    pub fn sweep_step(&mut self) -> Result<(), PinError<A::Error>> {
+        let next = match self.state.segments.bits() {
+            0x40 => 0,
+            bits if bits.is_power_of_two() => bits << 1,
+            _ => 1,
+        };
+        self.set_segments(Segments::from_bits(next))
+    }
+
+    /// Returns an adapter implementing `core::fmt::Write`.
+    ///
+    /// This allows you to use `write!` with the display, e.g. `write!(display.writer(), "{}",
+    /// digit)`. If the `ufmt` feature is enabled the adapter also implements `ufmt::uWrite` so
+    /// that `uwrite!` can be used instead, which avoids pulling `core::fmt` into the binary.
+    /// Unlike with `core::fmt` the error says which character is unsupported.
+    pub fn writer(&mut self) -> Writer<'_, Self> {
+        Writer(self)
+    }
+
+    /// Turns off all segments.
+    ///
+    /// The decimal point, if present, is not affected.
+    pub fn blank(&mut self) -> Result<(), PinError<A::Error>> {
+        self.set_segments(Segments::default())
+    }
+
+    /// Turns off all segments including the decimal point and disables the digit.
+    ///
+    /// Call this right after constructing the display so that it starts blank instead of showing
+    /// whatever the pins happened to be at power-on. If your HAL allows choosing the initial
+    /// level when configuring the pins as outputs, set them to the off level there as well to
+    /// avoid even the brief flash before this is called.
+    pub fn initialize(&mut self) -> Result<(), PinError<A::Error>> {
+        self.blank()?;
+        let cathode = Common::is_cathode(self.state.polarity);
+        self.dp
+            .write_segment(false, cathode)
+            .map_err(PinError::at(Segment::Dp))
+    }
+
+    /// Turns everything off and deconstructs the display, returning all pins.
+    ///
+    /// This is the safe counterpart of `into_pins`: the segments and the decimal point are turned
+    /// off and the digit is disabled first, so nothing is left lit. The returned tuple contains the
+    /// segment pins, the decimal point pin and the common pin, `NoPin` for those that aren't
+    /// connected. If writing fails the display is returned along with the error so that you can
+    /// retry.
+    #[allow(clippy::type_complexity)]
+    pub fn try_into_pins(
+        mut self,
+    ) -> Result<(SevenSegmentPins<A, B, C, D, E, F, G>, DP, CommonPin), (Self, PinError<A::Error>)>
+    {
+        if let Err(error) = self.initialize() {
+            return Err((self, error));
+        }
+        let pins = SevenSegmentPins {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            f: self.f,
+            g: self.g,
+        };
+        Ok((pins, self.dp, self.common_pin))
+    }
+
+    /// Sets the value of the display to a hexadecimal digit.
+    ///
+    /// The valid values are 0-15, letters are shown as `A b C d E F` so that `b` and `d` can't be
+    /// confused with `8` and `0`. In case of invalid value, the display will be blank or this
+    /// panics if the `strict` feature is enabled. Unlike `set` this shows the hexadecimal digits
+    /// even if the range was set to `ValueRange::Decimal`.
+    pub fn set_hex(&mut self, value: u8) -> Result<(), PinError<A::Error>> {
+        check_value(value, 0xf);
+        self.set_segments(self.hex_glyph(value))?;
+        if value <= 0xf {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux> core::fmt::Write
+    for Writer<'_, SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            self.0.set_char(c).map_err(|_| core::fmt::Error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux> ufmt::uWrite
+    for Writer<'_, SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    type Error = SetError<UnsupportedChar, PinError<A::Error>>;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for c in s.chars() {
+            self.0.set_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<A, B, C, D, E, F, G, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, DynamicPolarity, DP, CommonPin, Observer, Aux>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    /// Changes the polarity of the display.
+    ///
+    /// The currently shown segments are written again using the new polarity. The decimal point,
+    /// if present, is not affected so you need to call `set_dp` to fix it.
+    pub fn set_polarity(&mut self, polarity: PolarityKind) -> Result<(), PinError<A::Error>> {
+        self.state.polarity = polarity;
+        self.write(self.state.segments)
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    DP: OutputPin,
+    Common: Polarity,
+{
+    /// Lights or turns off the decimal point.
+    ///
+    /// The decimal point is only affected by this method, `set_with_dp` and `set_raw_u8` so it
+    /// stays as it is until you call one of them again. Errors are reported at `Segment::Dp` just
+    /// like in the other methods writing the pin.
+    pub fn set_dp(&mut self, on: bool) -> Result<(), PinError<DP::Error>> {
+        self.state.pending_dp = None;
+        write_segment(&mut self.dp, on, Common::is_cathode(self.state.polarity))
+            .map_err(PinError::at(Segment::Dp))
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    Aux: AuxPins,
+    Common: Polarity,
+{
+    /// Lights or turns off the auxiliary indicator at `index`.
+    ///
+    /// Like the decimal point, the indicators aren't affected by other methods. If `index` is out
+    /// of range an error is returned and no pin is written.
+    pub fn set_aux(
+        &mut self,
+        index: usize,
+        on: bool,
+    ) -> Result<(), SetError<InvalidAuxIndex, Aux::Error>> {
+        let cathode = Common::is_cathode(self.state.polarity);
+        self.aux
+            .write(index, on, cathode)
+            .ok_or(SetError::Invalid(InvalidAuxIndex(index)))?
+            .map_err(SetError::Pin)
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OutputPin<Error = A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    /// Sets the value of the display together with the decimal point.
+    ///
+    /// The decimal point is written in the same pass as the segments, right after them and before
+    /// the digit is enabled through the common pin, if there's one. The pins are still written one
+    /// by one so the change isn't atomic, but the decimal point changes together with the digit
+    /// rather than after a separate call. This is handy for showing fixed-point numbers. Invalid
+    /// values are handled the same way as in `set`.
+    pub fn set_with_dp(&mut self, value: u8, dp: bool) -> Result<(), PinError<A::Error>> {
+        let segments = self.checked_glyph(value);
+        self.state.value = None;
+        self.state.segments = segments;
+        self.write_with_dp(segments, Some(dp))?;
+        if value <= self.max_value() {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux> SegmentSink
+    for SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    type Error = PinError<A::Error>;
+
+    fn set_digit(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.set(value)
+    }
+
+    fn set_segments(&mut self, segments: Segments) -> Result<(), Self::Error> {
+        SevenSegment::set_segments(self, segments)
+    }
+
+    fn blank(&mut self) -> Result<(), Self::Error> {
+        SevenSegment::blank(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    pub(crate) struct TestPin(pub(crate) u8);
+
+    impl TestPin {
+        pub(crate) fn inv(&self) -> u8 {
+            if self.0 == 0 {
+                1
+            // Keep values > 1 in order to detect bugs
+            } else if self.0 == 1 {
+                0
+            } else {
+                self.0
+            }
+        }
+    }
+
+    impl super::OutputPin for &'_ mut TestPin {
+        type Error = core::convert::Infallible;
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0 = 1;
+            Ok(())
+        }
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0 = 0;
+            Ok(())
+        }
+    }
+
+    /// The highest value that can be passed to `set` without panicking.
+    const MAX_VALUE: u8 = if cfg!(feature = "strict") {
+        0xf
+    } else {
+        u8::MAX
+    };
+
+    type TestDisplay<'a, Common> = super::erased::SevenSegment<&'a mut TestPin, Common>;
+
+    type TestPins<'a> = super::SevenSegmentPins<
+        &'a mut TestPin,
+        &'a mut TestPin,
+        &'a mut TestPin,
+        &'a mut TestPin,
+        &'a mut TestPin,
+        &'a mut TestPin,
+        &'a mut TestPin,
+    >;
+
+    /// Passes unset pins to `f`, which builds the display, and returns the resulting pin levels.
+    fn levels(f: impl FnOnce(TestPins<'_>)) -> [u8; 7] {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        {
+            let [a, b, c, d, e, f_, g] = &mut pins;
+            f(super::SevenSegmentPins {
+                a,
+                b,
+                c,
+                d,
+                e,
+                f: f_,
+                g,
+            });
+        }
+        core::array::from_fn(|i| pins[i].0)
+    }
+
+    /// Runs `f` on a common cathode display and returns the resulting pin levels.
+    fn cathode(
+        f: impl FnOnce(&mut TestDisplay<'_, super::Cathode>),
+    ) -> (u8, u8, u8, u8, u8, u8, u8) {
+        let [a, b, c, d, e, f, g] = levels(|pins| f(&mut pins.with_common_cathode()));
+        (a, b, c, d, e, f, g)
+    }
+
+    /// Runs `f` on a common anode display and returns the inverted pin levels.
+    fn anode(f: impl FnOnce(&mut TestDisplay<'_, super::Anode>)) -> (u8, u8, u8, u8, u8, u8, u8) {
+        let [a, b, c, d, e, f, g] =
+            levels(|pins| f(&mut pins.with_common_anode())).map(|level| TestPin(level).inv());
+        (a, b, c, d, e, f, g)
+    }
+
+    fn test_digit(digit: u8, expected: (u8, u8, u8, u8, u8, u8, u8)) {
+        // We're using 2 to signal uninitialized;
+        let mut a = TestPin(2);
+        let mut b = TestPin(2);
+        let mut c = TestPin(2);
+        let mut d = TestPin(2);
+        let mut e = TestPin(2);
+        let mut f = TestPin(2);
+        let mut g = TestPin(2);
+
+        {
+            let mut seven_segment = super::SevenSegmentPins {
+                a: &mut a,
+                b: &mut b,
+                c: &mut c,
+                d: &mut d,
+                e: &mut e,
+                f: &mut f,
+                g: &mut g,
+            }
+            .with_common_anode();
+
+            seven_segment.set(digit).expect("unable to set digit");
+        }
+
+        assert_eq!(
+            (
+                a.inv(),
+                b.inv(),
+                c.inv(),
+                d.inv(),
+                e.inv(),
+                f.inv(),
+                g.inv()
+            ),
+            expected
+        );
+
+        let mut a = TestPin(2);
+        let mut b = TestPin(2);
+        let mut c = TestPin(2);
+        let mut d = TestPin(2);
+        let mut e = TestPin(2);
+        let mut f = TestPin(2);
+        let mut g = TestPin(2);
+
+        {
+            let mut seven_segment = super::SevenSegmentPins {
+                a: &mut a,
+                b: &mut b,
+                c: &mut c,
+                d: &mut d,
+                e: &mut e,
+                f: &mut f,
+                g: &mut g,
+            }
+            .with_common_cathode();
+
+            seven_segment.set(digit).expect("unable to set digit");
+        }
+
+        assert_eq!((a.0, b.0, c.0, d.0, e.0, f.0, g.0), expected);
+    }
+
+    #[test]
+    fn digit_0() {
+        test_digit(0, (1, 1, 1, 1, 1, 1, 0));
+    }
+
+    #[test]
+    fn digit_1() {
+        test_digit(1, (0, 1, 1, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn digit_2() {
+        test_digit(2, (1, 1, 0, 1, 1, 0, 1));
+    }
+
+    #[test]
+    fn digit_3() {
+        test_digit(3, (1, 1, 1, 1, 0, 0, 1));
+    }
+
+    #[test]
+    fn digit_4() {
+        test_digit(4, (0, 1, 1, 0, 0, 1, 1));
+    }
+
+    #[test]
+    fn digit_5() {
+        test_digit(5, (1, 0, 1, 1, 0, 1, 1));
+    }
+
+    #[test]
+    fn digit_6() {
+        test_digit(6, (1, 0, 1, 1, 1, 1, 1));
+    }
+
+    #[test]
+    fn digit_7() {
+        test_digit(7, (1, 1, 1, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn digit_8() {
+        test_digit(8, (1, 1, 1, 1, 1, 1, 1));
+    }
+
+    #[test]
+    fn digit_9() {
+        test_digit(9, (1, 1, 1, 1, 0, 1, 1));
+    }
+
+    #[test]
+    fn digit_a() {
+        test_digit(0xa, (1, 1, 1, 0, 1, 1, 1));
+    }
+    #[test]
+    fn digit_b() {
+        test_digit(0xb, (0, 0, 1, 1, 1, 1, 1));
+    }
+
+    #[test]
+    fn digit_c() {
+        test_digit(0xc, (1, 0, 0, 1, 1, 1, 0));
+    }
+
+    #[test]
+    fn digit_d() {
+        test_digit(0xd, (0, 1, 1, 1, 1, 0, 1));
+    }
+
+    #[test]
+    fn digit_e() {
+        test_digit(0xe, (1, 0, 0, 1, 1, 1, 1));
+    }
+
+    #[test]
+    fn digit_f() {
+        test_digit(0xf, (1, 0, 0, 0, 1, 1, 1));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "strict", should_panic)]
+    fn digit_invalid() {
+        test_digit(0x10, (0, 0, 0, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn hex() {
+        for value in 0..=MAX_VALUE {
+            let expected = cathode(|display| display.set(value).unwrap());
+            assert_eq!(cathode(|display| display.set_hex(value).unwrap()), expected);
+            let expected = anode(|display| display.set(value).unwrap());
+            assert_eq!(anode(|display| display.set_hex(value).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn hex_lowercase_b_d() {
+        assert_eq!(
+            cathode(|display| display.set_hex(0xb).unwrap()),
+            (0, 0, 1, 1, 1, 1, 1)
+        );
+        assert_eq!(
+            cathode(|display| display.set_hex(0xd).unwrap()),
+            (0, 1, 1, 1, 1, 0, 1)
+        );
+        if !cfg!(feature = "strict") {
+            assert_eq!(
+                anode(|display| display.set_hex(0x10).unwrap()),
+                (0, 0, 0, 0, 0, 0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn set_segments() {
+        let minus = super::Segments {
+            g: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            cathode(|display| display.set_segments(minus).unwrap()),
+            (0, 0, 0, 0, 0, 0, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_segments(minus).unwrap()),
+            (0, 0, 0, 0, 0, 0, 1)
+        );
+
+        let spinner = super::Segments {
+            a: true,
+            d: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            cathode(|display| display.set_segments(spinner).unwrap()),
+            (1, 0, 0, 1, 0, 0, 0)
+        );
+        assert_eq!(
+            anode(|display| display.set_segments(spinner).unwrap()),
+            (1, 0, 0, 1, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn dp() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let [a, b, c, d, e, f, g, dp] = &mut pins;
+        let mut seven_segment = super::SevenSegmentPinsWithDp {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+            dp,
+        }
+        .with_common_anode();
+
+        seven_segment.set(8).unwrap();
+        assert_eq!(seven_segment.dp.0, 2);
+        seven_segment.set_dp(true).unwrap();
+        assert_eq!(seven_segment.dp.0, 0);
+        seven_segment.set(1).unwrap();
+        assert_eq!(seven_segment.dp.0, 0);
+        seven_segment.set_dp(false).unwrap();
+        assert_eq!(seven_segment.dp.0, 1);
+    }
+
+    #[test]
+    fn set_with_dp() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let [a, b, c, d, e, f, g, dp] = &mut pins;
+        let mut seven_segment = super::SevenSegmentPinsWithDp {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+            dp,
+        }
+        .with_common_cathode();
+
+        seven_segment.set_with_dp(7, true).unwrap();
+        assert_eq!(seven_segment.current(), Some(7));
+        assert_eq!(seven_segment.dp.0, 1);
+        seven_segment.set_with_dp(3, false).unwrap();
+        assert_eq!(seven_segment.dp.0, 0);
+        seven_segment.set_one(super::Segment::Dp, true).unwrap();
+        assert_eq!(seven_segment.dp.0, 1);
+        assert_eq!(seven_segment.current(), Some(3));
+
+        seven_segment.freeze();
+        seven_segment.set_with_dp(7, false).unwrap();
+        assert_eq!(seven_segment.dp.0, 1);
+        assert_eq!(seven_segment.g.0, 1);
+        assert_eq!(seven_segment.current(), Some(7));
+        seven_segment.thaw().unwrap();
+        assert_eq!(seven_segment.dp.0, 0);
+        assert_eq!(seven_segment.g.0, 0);
+    }
+
+    #[test]
+    fn common_pin() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let [a, b, c, d, e, f, g, common] = &mut pins;
+        let mut seven_segment = super::SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+        .with_common_cathode()
+        .with_common_pin(common);
+
+        assert_eq!(seven_segment.common_pin.0, 2);
+        seven_segment.set(8).unwrap();
+        assert_eq!(seven_segment.common_pin.0, 0);
+        seven_segment.blank().unwrap();
+        assert_eq!(seven_segment.common_pin.0, 1);
+        seven_segment.set_ordered(1).unwrap();
+        assert_eq!(seven_segment.common_pin.0, 0);
+
+        let (mut seven_segment, common) = seven_segment.release_common_pin();
+        seven_segment.blank().unwrap();
+        assert_eq!(common.0, 0);
+    }
+
+    #[test]
+    fn blank() {
+        let expected = (0, 0, 0, 0, 0, 0, 0);
+        assert_eq!(cathode(|display| display.blank().unwrap()), expected);
+        assert_eq!(anode(|display| display.blank().unwrap()), expected);
+        assert_eq!(
+            cathode(|display| {
+                display.set(8).unwrap();
+                display.blank().unwrap();
+            }),
+            expected
+        );
+    }
+
+    #[test]
+    fn set_char() {
+        assert_eq!(
+            cathode(|display| display.set_char('H').unwrap()),
+            (0, 1, 1, 0, 1, 1, 1)
+        );
+        assert_eq!(
+            cathode(|display| display.set_char('h').unwrap()),
+            (0, 0, 1, 0, 1, 1, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_char('E').unwrap()),
+            (1, 0, 0, 1, 1, 1, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_char('e').unwrap()),
+            (1, 0, 0, 1, 1, 1, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_char('P').unwrap()),
+            (1, 1, 0, 0, 1, 1, 1)
+        );
+        assert_eq!(
+            cathode(|display| display.set_char('°').unwrap()),
+            (1, 1, 0, 0, 0, 1, 1)
+        );
+        assert_eq!(
+            cathode(|display| display.set_char('C').unwrap()),
+            (1, 0, 0, 1, 1, 1, 0)
+        );
+        assert_eq!(
+            cathode(|display| display.set_char('F').unwrap()),
+            (1, 0, 0, 0, 1, 1, 1)
+        );
+        for digit in 0..10 {
+            let c = char::from(b'0' + digit);
+            let expected = cathode(|display| display.set(digit).unwrap());
+            assert_eq!(cathode(|display| display.set_char(c).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn set_char_unsupported() {
+        for c in ['k', 'W', 'x', '!'] {
+            let levels = cathode(|display| {
+                display.set(8).unwrap();
+                assert_eq!(
+                    display.set_char(c),
+                    Err(super::SetError::Invalid(super::UnsupportedChar(c)))
+                );
+            });
+            assert_eq!(levels, (1, 1, 1, 1, 1, 1, 1));
+        }
+    }
+
+    #[test]
+    fn try_set() {
+        for digit in 0..10 {
+            let expected = cathode(|display| display.set(digit).unwrap());
+            assert_eq!(cathode(|display| display.try_set(digit).unwrap()), expected);
+        }
+
+        for value in [10, 15, 16, 255] {
+            let levels = anode(|display| {
+                display.set(8).unwrap();
+                assert_eq!(
+                    display.try_set(value),
+                    Err(super::SetError::Invalid(super::InvalidDigit(value)))
+                );
+            });
+            assert_eq!(levels, (1, 1, 1, 1, 1, 1, 1));
+        }
+    }
+
+    #[test]
+    fn current() {
+        cathode(|display| {
+            assert_eq!(display.current(), None);
+            display.set(4).unwrap();
+            assert_eq!(display.current(), Some(4));
+            display.set_hex(0xc).unwrap();
+            assert_eq!(display.current(), Some(0xc));
+            display.try_set(10).unwrap_err();
+            assert_eq!(display.current(), Some(0xc));
+            if !cfg!(feature = "strict") {
+                display.set(42).unwrap();
+                assert_eq!(display.current(), None);
+            }
+            display.set(7).unwrap();
+            display.blank().unwrap();
+            assert_eq!(display.current(), None);
+            display.set(7).unwrap();
+            display.set_char('E').unwrap();
+            assert_eq!(display.current(), None);
+        });
+    }
+
+    #[test]
+    fn brightness() {
+        let mut lit = 0;
+        let mut ticks = 0;
+        let levels = cathode(|display| {
+            display.set(1).unwrap();
+            display.set_brightness(64);
+            for _ in 0..(255 * 4) {
+                display.tick().unwrap();
+                ticks += 1;
+                if display.b.0 == 1 {
+                    lit += 1;
+                }
+                // unlit segments stay unlit
+                assert_eq!(display.a.0, 0);
+            }
+            assert_eq!(display.current(), Some(1));
+        });
+        assert_eq!(lit, 64 * 4);
+        assert_eq!(ticks, 255 * 4);
+        assert_eq!(levels, (0, 1, 1, 0, 0, 0, 0));
+
+        let levels = anode(|display| {
+            display.set(8).unwrap();
+            display.set_brightness(0);
+            for _ in 0..255 {
+                display.tick().unwrap();
+                assert_eq!(
+                    display.state.segments,
+                    super::Segments::from_mask((1, 1, 1, 1, 1, 1, 1))
+                );
+                assert_eq!(display.g.0, 1);
+            }
+            display.set_brightness(255);
+            display.tick().unwrap();
+        });
+        assert_eq!(levels, (1, 1, 1, 1, 1, 1, 1));
+    }
+
+    #[test]
+    fn writer() {
+        use core::fmt::Write;
+
+        assert_eq!(
+            cathode(|display| write!(display.writer(), "{}", 7).unwrap()),
+            (1, 1, 1, 0, 0, 0, 0)
+        );
+        assert_eq!(
+            anode(|display| write!(display.writer(), "{}", 42).unwrap()),
+            (1, 1, 0, 1, 1, 0, 1)
+        );
+        cathode(|display| {
+            display.writer().write_str("k").unwrap_err();
+        });
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn ufmt_writer() {
+        use super::{SetError, UnsupportedChar};
+
+        assert_eq!(
+            cathode(|display| ufmt::uwrite!(display.writer(), "{}", 7u8).unwrap()),
+            (1, 1, 1, 0, 0, 0, 0)
+        );
+        cathode(|display| {
+            assert_eq!(
+                ufmt::uwrite!(display.writer(), "k"),
+                Err(SetError::Invalid(UnsupportedChar('k')))
+            );
+        });
+    }
+
+    #[test]
+    fn digit_tables() {
+        use super::Segments;
+
+        for value in 0..=MAX_VALUE {
+            let from_table = cathode(|display| {
+                display
+                    .set_segments(Segments::from_hex_digit(value).unwrap_or_default())
+                    .unwrap()
+            });
+            assert_eq!(cathode(|display| display.set(value).unwrap()), from_table);
+            assert_eq!(Segments::from_digit(value).is_some(), value < 10);
+        }
+        assert_eq!(Segments::DIGITS[..], Segments::HEX_DIGITS[..10]);
+    }
+
+    #[test]
+    fn set_all() {
+        assert_eq!(
+            cathode(|display| display.set_all(true).unwrap()),
+            (1, 1, 1, 1, 1, 1, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_all(true).unwrap()),
+            (1, 1, 1, 1, 1, 1, 1)
+        );
+        assert_eq!(
+            cathode(|display| display.set_all(false).unwrap()),
+            (0, 0, 0, 0, 0, 0, 0)
+        );
+        assert_eq!(
+            anode(|display| display.set_all(false).unwrap()),
+            (0, 0, 0, 0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn invert() {
+        assert_eq!(
+            cathode(|display| {
+                display.set(1).unwrap();
+                display.invert().unwrap();
+            }),
+            (1, 0, 0, 1, 1, 1, 1)
+        );
+        assert_eq!(
+            anode(|display| {
+                display.set(7).unwrap();
+                display.invert().unwrap();
+                display.invert().unwrap();
+            }),
+            (1, 1, 1, 0, 0, 0, 0)
+        );
+        assert_eq!(
+            anode(|display| display.invert().unwrap()),
+            (1, 1, 1, 1, 1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn set_minus() {
+        assert_eq!(
+            cathode(|display| display.set_minus().unwrap()),
+            (0, 0, 0, 0, 0, 0, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_minus().unwrap()),
+            (0, 0, 0, 0, 0, 0, 1)
+        );
+        assert_eq!(
+            cathode(|display| display.set_char('-').unwrap()),
+            cathode(|display| display.set_minus().unwrap())
+        );
+    }
+
+    #[test]
+    fn into_pins() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let [a, b, c, d, e, f, g, dp] = &mut pins;
+        let mut display = super::SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+        .with_common_cathode();
+        display.set(1).unwrap();
+        let pins = display.into_pins();
+        assert_eq!((pins.a.0, pins.b.0), (0, 1));
+
+        let mut display = super::SevenSegmentPinsWithDp {
+            a: pins.a,
+            b: pins.b,
+            c: pins.c,
+            d: pins.d,
+            e: pins.e,
+            f: pins.f,
+            g: pins.g,
+            dp,
+        }
+        .with_common_cathode();
+        display.set_dp(true).unwrap();
+        let pins = display.into_pins();
+        assert_eq!((pins.a.0, pins.b.0, pins.dp.0), (0, 1, 1));
+    }
+
+    #[test]
+    fn polarity() {
+        use super::{Anode, Cathode, PolarityKind, StaticPolarity};
+
+        const _: () = assert!(Anode::IS_ANODE && !Cathode::IS_ANODE);
+        cathode(|display| assert_eq!(display.polarity(), PolarityKind::Cathode));
+        anode(|display| assert_eq!(display.polarity(), PolarityKind::Anode));
+    }
+
+    #[test]
+    fn dynamic_polarity() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let [a, b, c, d, e, f, g] = &mut pins;
+        let mut display = super::SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+        .with_polarity(super::PolarityKind::Cathode);
+        display.set(1).unwrap();
+        assert_eq!((display.a.0, display.b.0), (0, 1));
+        display.set_polarity(super::PolarityKind::Anode).unwrap();
+        assert_eq!((display.a.0, display.b.0), (1, 0));
+        assert_eq!(display.polarity(), super::PolarityKind::Anode);
+        display.set(7).unwrap();
+        assert_eq!((display.a.0, display.d.0), (0, 1));
+    }
+
+    #[test]
+    fn builder() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        {
+            let [a, b, c, d, e, f, g] = &mut pins;
+            // intentionally out of order
+            let mut display = super::SevenSegmentBuilder::new()
+                .g(g)
+                .a(a)
+                .c(c)
+                .b(b)
+                .e(e)
+                .d(d)
+                .f(f)
+                .build()
+                .with_common_cathode();
+            display.set(2).unwrap();
+        }
+        let levels: [u8; 7] = core::array::from_fn(|i| pins[i].0);
+        assert_eq!(levels, [1, 1, 0, 1, 1, 0, 1]);
+    }
+
+    struct TestDelay(u32);
+
+    impl embedded_hal::blocking::delay::DelayMs<u16> for TestDelay {
+        fn delay_ms(&mut self, ms: u16) {
+            self.0 += u32::from(ms);
+        }
+    }
+
+    impl embedded_hal::blocking::delay::DelayUs<u16> for TestDelay {
+        fn delay_us(&mut self, us: u16) {
+            self.0 += u32::from(us);
+        }
+    }
+
+    #[test]
+    fn staggered() {
+        use super::Segments;
+        use crate::mock::MockDisplay;
+
+        let mut delay = TestDelay(0);
+        let mut display = MockDisplay::<super::Anode>::new().into_inner();
+        display.set_staggered(8, 3, &mut delay, 10).unwrap();
+        assert_eq!(display.current(), Some(8));
+        assert_eq!(delay.0, 20);
+        display
+            .set_segments_staggered(Segments::MINUS, 0, &mut delay, 10)
+            .unwrap();
+        assert_eq!(display.segments(), Segments::MINUS);
+        assert_eq!(delay.0, 20);
+        let pins = display.into_pins();
+        assert_eq!(pins.a.is_high(), Some(true));
+        assert_eq!(pins.g.is_high(), Some(false));
+    }
+
+    #[test]
+    fn self_test() {
+        use crate::mock::MockDisplay;
+
+        let mut delay = TestDelay(0);
+        let mut lit = 0;
+        let mut display = MockDisplay::<super::Anode>::new()
+            .into_inner()
+            .with_observer(|segments: super::Segments| lit += u32::from(segments.g));
+        display.self_test(3, &mut delay, 100).unwrap();
+        assert_eq!(display.segments(), super::Segments::default());
+        assert_eq!(lit, 3);
+        assert_eq!(delay.0, 600);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn render_ascii() {
+        use super::Segments;
+        use crate::mock::MockDisplay;
+
+        let mut display = MockDisplay::<super::Cathode>::new().into_inner();
+        display.set(3).unwrap();
+        assert_eq!(
+            display.render_ascii(),
+            " ____ \n     |\n ____|\n     |\n ____|"
+        );
+        assert_eq!(
+            Segments::DIGITS[8].render_ascii(),
+            " ____ \n|    |\n|____|\n|    |\n|____|"
+        );
+        assert_eq!(Segments::default().render_ascii().trim(), "");
+    }
+
+    #[test]
+    fn active_level_constructors() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        {
+            let [a, b, c, d, e, f, g] = &mut pins;
+            let mut display = super::SevenSegmentPins {
+                a,
+                b,
+                c,
+                d,
+                e,
+                f,
+                g,
+            }
+            .with_active_high_segments();
+            display.set(1).unwrap();
+        }
+        let levels: [u8; 7] = core::array::from_fn(|i| pins[i].0);
+        assert_eq!(levels, [0, 1, 1, 0, 0, 0, 0]);
+
+        {
+            let [a, b, c, d, e, f, g] = &mut pins;
+            let mut display = super::SevenSegmentPins {
+                a,
+                b,
+                c,
+                d,
+                e,
+                f,
+                g,
+            }
+            .with_active_low_segments();
+            display.set(1).unwrap();
+        }
+        let levels: [u8; 7] = core::array::from_fn(|i| pins[i].0);
+        assert_eq!(levels, [1, 0, 0, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn infallible() {
+        let mut display = super::SevenSegmentPins {
+            a: StuckPin(true),
+            b: StuckPin(true),
+            c: StuckPin(true),
+            d: StuckPin(true),
+            e: StuckPin(true),
+            f: StuckPin(true),
+            g: StuckPin(true),
+        }
+        .into_infallible()
+        .with_common_cathode();
+        let result: Result<(), super::PinError<core::convert::Infallible>> = display.set(8);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn digit() {
+        use super::{Digit, InvalidDigit, UnsupportedChar};
+        use core::convert::TryFrom;
+
+        assert_eq!(Digit::try_from('7').map(Digit::get), Ok(7));
+        assert_eq!(Digit::try_from('a'), Err(UnsupportedChar('a')));
+        assert_eq!(Digit::try_from(9u8).map(u8::from), Ok(9));
+        assert_eq!(Digit::try_from(10u8), Err(InvalidDigit(10)));
+        for value in 0..=9 {
+            let digit = Digit::new(value).unwrap();
+            assert_eq!(
+                cathode(|display| display.set_value(digit).unwrap()),
+                cathode(|display| display.set(value).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn from_tuple_and_array() {
+        use super::SevenSegmentPins;
+        use crate::mock::MockPin;
+
+        let pins = SevenSegmentPins::from_array(core::array::from_fn::<_, 7, _>(|i| i));
+        assert_eq!((pins.a, pins.d, pins.g), (0, 3, 6));
+        let pins = SevenSegmentPins::from_tuple((0u8, 1u16, 2u32, 3u64, 4i8, 5i16, 6i32));
+        assert_eq!((pins.a, pins.d, pins.g), (0, 3, 6));
+
+        let mut display =
+            SevenSegmentPins::from_array([MockPin::default(); 7]).with_common_cathode();
+        display.set(1).unwrap();
+        assert_eq!(display.b.is_high(), Some(true));
+    }
+
+    #[test]
+    fn set_opt() {
+        for value in 0..=MAX_VALUE {
+            assert_eq!(
+                cathode(|display| display.set_opt(Some(value)).unwrap()),
+                cathode(|display| display.set(value).unwrap())
+            );
+        }
+        assert_eq!(
+            cathode(|display| {
+                display.set(8).unwrap();
+                display.set_opt(None).unwrap();
+                assert_eq!(display.current(), None);
+            }),
+            (0, 0, 0, 0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn bar() {
+        use super::Segments;
+        use crate::mock::MockDisplay;
+
+        assert_eq!(Segments::bar(0), Segments::default());
+        assert_eq!(Segments::bar(2), Segments::from_mask((1, 1, 0, 0, 0, 0, 0)));
+        assert_eq!(Segments::bar(6), Segments::from_mask((1, 1, 1, 1, 1, 1, 0)));
+        assert_eq!(Segments::bar(7), Segments::DIGITS[8]);
+        assert_eq!(Segments::bar(200), Segments::DIGITS[8]);
+
+        let mut display = MockDisplay::<super::Cathode>::new().into_inner();
+        let mut counts = [0; 9];
+        for (count, percent) in counts.iter_mut().zip([0, 14, 15, 50, 85, 86, 99, 100, 255]) {
+            display.set_bar(percent).unwrap();
+            *count = display.segments().bits().count_ones();
+        }
+        assert_eq!(counts, [0, 0, 1, 3, 5, 6, 6, 7, 7]);
+    }
+
+    #[test]
+    fn freeze() {
+        use super::{Segment, Segments};
+        use crate::mock::MockDisplay;
+
+        let mut observed = 0;
+        {
+            let mut display = MockDisplay::<super::Cathode>::new()
+                .into_inner()
+                .with_observer(|_| observed += 1);
+            display.set(1).unwrap();
+            display.freeze();
+            assert!(display.is_frozen());
+            display.set(7).unwrap();
+            display.set_segments_ordered(Segments::MINUS).unwrap();
+            display.set_one(Segment::A, true).unwrap();
+            display.set(4).unwrap();
+            assert_eq!(display.current(), Some(4));
+            assert_eq!(display.f.is_high(), Some(false));
+            display.thaw().unwrap();
+            assert!(!display.is_frozen());
+            assert_eq!(display.f.is_high(), Some(true));
+            display.set(8).unwrap();
+            assert_eq!(display.e.is_high(), Some(true));
+        }
+        assert_eq!(observed, 3);
+    }
+
+    #[test]
+    fn show_two() {
+        use super::Segments;
+        use crate::mock::MockDisplay;
+
+        let mut delay = TestDelay(0);
+        let mut shown = [Segments::DIGITS[8]; 4];
+        let mut index = 0;
+        {
+            let mut display = MockDisplay::<super::Anode>::new()
+                .into_inner()
+                .with_observer(|segments| {
+                    shown[index] = segments;
+                    index += 1;
+                });
+            display.show_two(4, 2, &mut delay, 400).unwrap();
+            assert_eq!(display.current(), None);
+        }
+        assert_eq!(delay.0, 1000);
+        let (d, blank) = (Segments::DIGITS, Segments::default());
+        assert_eq!(shown, [d[4], blank, d[2], blank]);
+    }
+
+    #[test]
+    fn demo_count() {
+        use crate::mock::MockDisplay;
+
+        let mut delay = TestDelay(0);
+        let mut shown = 0;
+        let mut display = MockDisplay::<super::Cathode>::new()
+            .into_inner()
+            .with_observer(|_| shown += 1);
+        display.demo_count(&mut delay, 250).unwrap();
+        assert_eq!(display.current(), Some(9));
+        assert_eq!(shown, 10);
+        assert_eq!(delay.0, 2500);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "strict", should_panic)]
+    fn decimal_range() {
+        use super::{Segments, ValueRange};
+        use crate::mock::MockDisplay;
+
+        let mut display = MockDisplay::<super::Cathode>::new()
+            .into_inner()
+            .with_value_range(ValueRange::Decimal);
+        display.set(9).unwrap();
+        assert_eq!(display.current(), Some(9));
+        display.set_hex(0xa).unwrap();
+        assert_eq!(Some(display.segments()), Segments::from_hex_digit(0xa));
+        assert_eq!(display.current(), Some(0xa));
+        display.set(10).unwrap();
+        assert_eq!(display.segments(), Segments::default());
+        assert_eq!(display.current(), None);
+    }
+
+    #[test]
+    fn animation() {
+        use super::Segments;
+
+        let frames = [
+            Segments::from_mask((1, 0, 0, 0, 0, 0, 0)),
+            Segments::from_mask((0, 0, 0, 0, 0, 0, 1)),
+            Segments::from_mask((0, 0, 0, 1, 0, 0, 0)),
+        ];
+        let mut looped = frames.iter().copied().cycle();
+        let levels = cathode(|display| {
+            for _ in 0..4 {
+                assert!(display.step(&mut looped).unwrap());
+            }
+            assert!(!display.step(&mut core::iter::empty()).unwrap());
+        });
+        assert_eq!(levels, (1, 0, 0, 0, 0, 0, 0));
+
+        let mut delay = TestDelay(0);
+        let levels = anode(|display| display.play(frames, &mut delay, 100).unwrap());
+        assert_eq!(levels, (0, 0, 0, 1, 0, 0, 0));
+        assert_eq!(delay.0, 300);
+    }
+
+    /// Pin recording the order of writes
+    struct OrderPin<'a> {
+        index: usize,
+        log: &'a core::cell::RefCell<[(usize, bool); 7]>,
+        count: &'a core::cell::Cell<usize>,
+    }
+
+    impl super::OutputPin for OrderPin<'_> {
+        type Error = core::convert::Infallible;
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.log.borrow_mut()[self.count.get()] = (self.index, true);
+            self.count.set(self.count.get() + 1);
+            Ok(())
+        }
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.log.borrow_mut()[self.count.get()] = (self.index, false);
+            self.count.set(self.count.get() + 1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn set_ordered() {
+        let log = core::cell::RefCell::new([(0, false); 7]);
+        let count = core::cell::Cell::new(0);
+        let pin = |index| OrderPin {
+            index,
+            log: &log,
+            count: &count,
+        };
+        let mut display = super::SevenSegmentPins {
+            a: pin(0),
+            b: pin(1),
+            c: pin(2),
+            d: pin(3),
+            e: pin(4),
+            f: pin(5),
+            g: pin(6),
+        }
+        .with_common_cathode();
+
+        display.set_ordered(2).unwrap();
+        assert_eq!(count.get(), 7);
+        assert_eq!(
+            *log.borrow(),
+            [
+                (2, false),
+                (5, false),
+                (0, true),
+                (1, true),
+                (3, true),
+                (4, true),
+                (6, true)
+            ]
+        );
+        assert_eq!(display.current(), Some(2));
+    }
+
+    #[test]
+    fn bits() {
+        use super::Segments;
+
+        for bits in 0..0x80 {
+            assert_eq!(Segments::from_bits(bits).bits(), bits);
+            assert_eq!(u8::from(Segments::from(bits | 0x80)), bits);
+        }
+        assert_eq!(
+            Segments::from_bits(0x01),
+            Segments::from_mask((1, 0, 0, 0, 0, 0, 0))
+        );
+        assert_eq!(Segments::from_bits(0x40), Segments::MINUS);
+        assert_eq!(Segments::DIGITS[7].bits(), 0b0000111);
+    }
+
+    #[test]
+    fn segments() {
+        use super::Segments;
+
+        anode(|display| {
+            assert_eq!(display.segments(), Segments::default());
+            display.set(3).unwrap();
+            assert_eq!(display.segments(), Segments::DIGITS[3]);
+            display.set_brightness(0);
+            display.tick().unwrap();
+            assert_eq!(display.segments(), Segments::DIGITS[3]);
+            display.set_char('H').unwrap();
+            assert!(!display.segments().a);
+            assert!(display.segments().b);
+        });
+    }
+
+    #[test]
+    fn rotation() {
+        use super::{Rotation, Segment, Segments};
+
+        let levels = levels(|pins| {
+            let mut seven_segment = pins.with_common_cathode().with_rotation(Rotation::Flip180);
+
+            seven_segment.set(7).unwrap();
+            assert_eq!(seven_segment.segments(), Segments::DIGITS[7]);
+            seven_segment.set_one(Segment::G, true).unwrap();
+            seven_segment.set_one(Segment::A, false).unwrap();
+            assert_eq!(seven_segment.segments(), Segments::from_bits(0b1000110));
+        });
+        assert_eq!(levels, [0, 0, 0, 0, 1, 1, 1]);
+
+        for digit in Segments::HEX_DIGITS {
+            let flipped = Rotation::Flip180.apply(digit);
+            assert_eq!(Rotation::Flip180.apply(flipped), digit);
+            assert_eq!(Rotation::Normal.apply(digit), digit);
+        }
+    }
+
+    #[test]
+    fn mirror() {
+        use super::{Mirror, Segment, Segments};
+
+        let levels = levels(|pins| {
+            let mut seven_segment = pins.with_common_cathode().with_mirror(Mirror::Horizontal);
+
+            seven_segment.set(7).unwrap();
+            assert_eq!(seven_segment.segments(), Segments::DIGITS[7]);
+            assert_eq!(seven_segment.f.0, 1);
+            seven_segment.set_one(Segment::B, false).unwrap();
+            seven_segment.set_one(Segment::G, true).unwrap();
+        });
+        assert_eq!(levels, [1, 0, 0, 0, 1, 0, 1]);
+
+        for digit in Segments::HEX_DIGITS {
+            let mirrored = Mirror::Horizontal.apply(digit);
+            assert_eq!(Mirror::Horizontal.apply(mirrored), digit);
+        }
+    }
+
+    #[test]
+    fn macro_construction() {
+        use crate::mock::MockPin;
+
+        let pin = MockPin::default;
+        let mut display = crate::seven_segment!(
+            cathode,
+            a: pin(),
+            b: pin(),
+            c: pin(),
+            d: pin(),
+            e: pin(),
+            f: pin(),
+            g: pin(),
+            dp: pin(),
+        );
+        display.set_with_dp(1, true).unwrap();
+        let pins = display.into_pins();
+        assert_eq!(pins.b.is_high(), Some(true));
+        assert_eq!(pins.dp.is_high(), Some(true));
+
+        let mut display = crate::seven_segment!(anode, array: [pin(); 7]);
+        display.set(1).unwrap();
+        assert_eq!(display.into_pins().b.is_high(), Some(false));
+    }
+
+    #[test]
+    fn blank_on_drop() {
+        use super::{BlankOnDrop, SegmentSink, Segments};
+        use crate::mock::MockDisplay;
+
+        let mut display = MockDisplay::<super::Cathode>::new().into_inner();
+        {
+            let mut guard = display.blank_on_drop();
+            guard.set(7).unwrap();
+            assert_eq!(guard.current(), Some(7));
+        }
+        assert_eq!(display.segments(), Segments::default());
+        assert_eq!(display.a.is_high(), Some(false));
+
+        let sink: &mut dyn SegmentSink<Error = _> = &mut display;
+        BlankOnDrop::new(sink).set_digit(1).unwrap();
+        assert_eq!(display.current(), None);
+    }
+
+    #[test]
+    fn dyn_sink() {
+        use super::{SegmentSink, Segments};
+        use crate::mock::MockDisplay;
+        use crate::multiplex::MultiplexedDisplay;
+
+        struct Ui<'a> {
+            displays: [&'a mut dyn SegmentSink<Error = ()>; 2],
+        }
+
+        let mut single = MockDisplay::<super::Cathode>::new()
+            .into_inner()
+            .map_err(|_| ());
+        let segments = MockDisplay::<super::Cathode>::new().into_inner();
+        let mut bank = MultiplexedDisplay::new(segments, [(); 2]).map_err(|error| match error {});
+        let ui = Ui {
+            displays: [&mut single, &mut bank],
+        };
+        for display in ui.displays {
+            display.set_digit(5).unwrap();
+        }
+        assert_eq!(single.into_inner().current(), Some(5));
+        assert_eq!(bank.into_inner().buffer()[1], Segments::DIGITS[5]);
+    }
+
+    #[test]
+    fn auto_traits() {
+        use crate::mock::{MockDisplay, MockPin, MockSevenSegment};
+        use crate::multiplex::MultiplexedDisplay;
+        use crate::port::PortSevenSegment;
+
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        assert_send_sync(&MockDisplay::<super::Anode>::new());
+        assert_send_sync(&MockDisplay::<super::CommonCathodeActiveHigh>::new());
+        let display =
+            super::erased::from_array([MockPin::default(); 7], super::PolarityKind::Anode);
+        assert_send_sync(&display);
+        let display = display.with_common_pin(MockPin::default());
+        assert_send_sync(&display);
+        let display = MultiplexedDisplay::<MockSevenSegment<super::Cathode>, MockPin, 2>::new(
+            MockDisplay::new().into_inner(),
+            [MockPin::default(); 2],
+        );
+        assert_send_sync(&display);
+        assert_send_sync(&PortSevenSegment::<u8, super::Anode>::new(0));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "strict", should_panic)]
+    fn invalid_glyph() {
+        use super::Segments;
+        use crate::mock::MockDisplay;
+
+        let mut display = MockDisplay::<super::Cathode>::new().into_inner();
+        display.set_invalid_glyph(Segments::MINUS);
+        display.set(3).unwrap();
+        assert_eq!(display.segments(), Segments::DIGITS[3]);
+        display.set_hex(16).unwrap();
+        assert_eq!(display.segments(), Segments::MINUS);
+        display.set(42).unwrap();
+        assert_eq!(display.segments(), Segments::MINUS);
+        assert_eq!(display.current(), None);
+    }
+
+    #[test]
+    fn digit_style() {
+        use super::{DigitStyle, Segments};
+        use crate::mock::MockDisplay;
+
+        let mut display = MockDisplay::<super::Cathode>::new()
+            .into_inner()
+            .with_digit_style(DigitStyle::Classic);
+        let mut shown = [Segments::default(); 3];
+        for (segments, value) in shown.iter_mut().zip([6, 7, 9]) {
+            display.set(value).unwrap();
+            *segments = display.segments();
+        }
+        assert_eq!(
+            shown,
+            [
+                Segments::from_mask((0, 0, 1, 1, 1, 1, 1)),
+                Segments::from_mask((1, 1, 1, 0, 0, 0, 0)),
+                Segments::from_mask((1, 1, 1, 0, 0, 1, 1)),
+            ]
+        );
+
+        let mut display = display.with_digit_style(DigitStyle::FullTail);
+        for (segments, value) in shown.iter_mut().zip([6, 7, 9]) {
+            display.set(value).unwrap();
+            *segments = display.segments();
+        }
+        assert_eq!(
+            shown,
+            [
+                Segments::from_mask((1, 0, 1, 1, 1, 1, 1)),
+                Segments::from_mask((1, 1, 1, 0, 0, 1, 0)),
+                Segments::from_mask((1, 1, 1, 1, 0, 1, 1)),
+            ]
+        );
+        display.set(8).unwrap();
+        assert_eq!(display.segments(), Segments::DIGITS[8]);
+    }
+
+    #[test]
+    fn hex_case() {
+        use super::{HexCase, Segments};
+        use crate::mock::MockDisplay;
+
+        let upper = [
+            Segments::from_mask((1, 1, 1, 0, 1, 1, 1)),
+            Segments::from_mask((0, 0, 1, 1, 1, 1, 1)),
+            Segments::from_mask((1, 0, 0, 1, 1, 1, 0)),
+            Segments::from_mask((0, 1, 1, 1, 1, 0, 1)),
+            Segments::from_mask((1, 0, 0, 1, 1, 1, 1)),
+            Segments::from_mask((1, 0, 0, 0, 1, 1, 1)),
+        ];
+        let mut lower = upper;
+        lower[2] = Segments::from_mask((0, 0, 0, 1, 1, 0, 1));
+        for (case, expected) in [(HexCase::Upper, upper), (HexCase::Lower, lower)] {
+            let mut display = MockDisplay::<super::Anode>::new()
+                .into_inner()
+                .with_hex_case(case);
+            let mut shown = [Segments::default(); 6];
+            for (segments, value) in shown.iter_mut().zip(10..) {
+                display.set_hex(value).unwrap();
+                *segments = display.segments();
             }
+            assert_eq!(shown, expected);
+        }
+        assert_eq!(HexCase::Upper.c(), Segments::from_char('C').unwrap());
+        assert_eq!(HexCase::Lower.c(), Segments::from_char('c').unwrap());
+    }
 
-            fn set_low(&mut self) -> Result<(), Self::Error> {
-                (*self).0 = 0;
+    #[test]
+    fn default_glyphs() {
+        use super::Segments;
+        use crate::mock::MockDisplay;
+
+        let mut display = MockDisplay::<super::Anode>::new().into_inner();
+        assert_eq!(display.glyphs(), Segments::HEX_DIGITS);
+        for (value, glyph) in (0..=MAX_VALUE).zip(display.glyphs()) {
+            display.set(value).unwrap();
+            assert_eq!(display.segments(), glyph);
+        }
+    }
+
+    #[test]
+    fn glyphs() {
+        use super::Segments;
+
+        let mut glyphs = Segments::HEX_DIGITS;
+        glyphs[6].a = false;
+        glyphs[9].d = false;
+        let nine = levels(|pins| {
+            let mut seven_segment = pins.with_common_cathode().with_glyphs(glyphs);
+
+            assert_eq!(seven_segment.glyphs(), glyphs);
+            seven_segment.set(6).unwrap();
+            assert_eq!(seven_segment.segments(), glyphs[6]);
+            assert_eq!(seven_segment.current(), Some(6));
+            seven_segment.set_ordered(9).unwrap();
+            assert_eq!(seven_segment.segments(), glyphs[9]);
+        });
+        assert_eq!(nine, [1, 1, 1, 0, 0, 1, 1]);
+        let last = levels(|pins| {
+            let mut seven_segment = pins.with_common_cathode().with_glyphs(glyphs);
+            seven_segment.set(8).unwrap();
+            assert_eq!(seven_segment.segments(), Segments::DIGITS[8]);
+            if !cfg!(feature = "strict") {
+                seven_segment.set(16).unwrap();
+                assert_eq!(seven_segment.segments(), Segments::default());
+            }
+        });
+        assert_eq!(last[0], u8::from(cfg!(feature = "strict")));
+    }
+
+    /// Pin that can't be driven high.
+    struct StuckPin(bool);
+
+    impl super::OutputPin for StuckPin {
+        type Error = &'static str;
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            if self.0 {
+                Err("stuck")
+            } else {
                 Ok(())
             }
         }
 
-        // We're using 2 to signal uninitialized;
-        let mut a = TestPin(2);
-        let mut b = TestPin(2);
-        let mut c = TestPin(2);
-        let mut d = TestPin(2);
-        let mut e = TestPin(2);
-        let mut f = TestPin(2);
-        let mut g = TestPin(2);
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
 
-        {
-            let mut seven_segment = super::SevenSegmentPins {
-                a: &mut a,
-                b: &mut b,
-                c: &mut c,
-                d: &mut d,
-                e: &mut e,
-                f: &mut f,
-                g: &mut g,
-            }
-            .with_common_anode();
+    #[test]
+    fn pin_error() {
+        use super::{PinError, Segment, SetError};
 
-            seven_segment.set(digit).expect("unable to set digit");
+        let mut display = super::SevenSegmentPins {
+            a: StuckPin(false),
+            b: StuckPin(false),
+            c: StuckPin(false),
+            d: StuckPin(false),
+            e: StuckPin(true),
+            f: StuckPin(false),
+            g: StuckPin(false),
         }
+        .with_common_cathode()
+        .with_common_pin(StuckPin(true));
 
-        assert_eq!(
-            (
-                a.inv(),
-                b.inv(),
-                c.inv(),
-                d.inv(),
-                e.inv(),
-                f.inv(),
-                g.inv()
-            ),
-            expected
-        );
+        let error = PinError {
+            segment: Segment::E,
+            source: "stuck",
+        };
+        assert_eq!(display.set(8), Err(error));
+        assert_eq!(display.try_set(2), Err(SetError::Pin(error)));
+        assert_eq!(display.set(1), Ok(()));
+        let error = PinError {
+            segment: Segment::Common,
+            source: "stuck",
+        };
+        assert_eq!(display.blank(), Err(error));
+    }
 
-        let mut a = TestPin(2);
-        let mut b = TestPin(2);
-        let mut c = TestPin(2);
-        let mut d = TestPin(2);
-        let mut e = TestPin(2);
-        let mut f = TestPin(2);
-        let mut g = TestPin(2);
+    #[test]
+    fn dp_error() {
+        use super::{PinError, Segment};
 
-        {
-            let mut seven_segment = super::SevenSegmentPins {
-                a: &mut a,
-                b: &mut b,
-                c: &mut c,
-                d: &mut d,
-                e: &mut e,
-                f: &mut f,
-                g: &mut g,
+        let mut display = super::SevenSegmentPinsWithDp {
+            a: StuckPin(false),
+            b: StuckPin(false),
+            c: StuckPin(false),
+            d: StuckPin(false),
+            e: StuckPin(false),
+            f: StuckPin(false),
+            g: StuckPin(false),
+            dp: StuckPin(true),
+        }
+        .with_common_cathode();
+        let error = PinError {
+            segment: Segment::Dp,
+            source: "stuck",
+        };
+        assert_eq!(display.set_dp(true), Err(error));
+        assert_eq!(display.set_with_dp(1, true), Err(error));
+        assert_eq!(display.set_dp(false), Ok(()));
+    }
+
+    #[test]
+    fn set_ascii() {
+        use super::{InvalidAsciiDigit, SetError};
+
+        for (i, c) in (b'0'..=b'9').enumerate() {
+            assert_eq!(
+                cathode(|display| display.set_ascii(c).unwrap()),
+                cathode(|display| display.set(i as u8).unwrap())
+            );
+        }
+        cathode(|display| {
+            display.set_ascii(b'4').unwrap();
+            for &c in b"/:aA \0" {
+                assert_eq!(
+                    display.set_ascii(c),
+                    Err(SetError::Invalid(InvalidAsciiDigit(c)))
+                );
             }
-            .with_common_cathode();
+            assert_eq!(display.current(), Some(4));
+        });
+    }
 
-            seven_segment.set(digit).expect("unable to set digit");
+    #[test]
+    fn sweep() {
+        use super::Segments;
+
+        anode(|display| {
+            display.set(8).unwrap();
+            for i in 0..7 {
+                display.sweep_step().unwrap();
+                assert_eq!(display.segments(), Segments::from_bits(1 << i));
+            }
+            display.sweep_step().unwrap();
+            assert_eq!(display.segments(), Segments::default());
+            display.sweep_step().unwrap();
+            assert_eq!(display.segments(), Segments::from_bits(1));
+        });
+    }
+
+    #[test]
+    fn observer() {
+        use super::{Segment, Segments};
+
+        let mut shown = [Segments::default(); 4];
+        let mut count = 0;
+        let levels = levels(|pins| {
+            let mut seven_segment = pins.with_common_anode().with_observer(|segments| {
+                shown[count] = segments;
+                count += 1;
+            });
+
+            seven_segment.set(3).unwrap();
+            seven_segment.set_ordered(4).unwrap();
+            seven_segment.set_one(Segment::A, true).unwrap();
+            seven_segment.blank().unwrap();
+        });
+        assert_eq!(levels, [1; 7]);
+        let mut four_with_a = Segments::DIGITS[4];
+        four_with_a.a = true;
+        assert_eq!(count, 4);
+        assert_eq!(
+            shown,
+            [
+                Segments::DIGITS[3],
+                Segments::DIGITS[4],
+                four_with_a,
+                Segments::default()
+            ]
+        );
+    }
+
+    #[test]
+    fn expected_segments() {
+        for value in 0..=MAX_VALUE {
+            let bits = super::expected_segments(value).unwrap_or_default();
+            let (a, b, c, d, e, f, g) = cathode(|display| display.set(value).unwrap());
+            assert_eq!(
+                [a == 1, b == 1, c == 1, d == 1, e == 1, f == 1, g == 1],
+                bits
+            );
         }
+        assert_eq!(super::expected_segments(8), Some([true; 7]));
+        assert_eq!(super::expected_segments(16), None);
+    }
 
-        assert_eq!((a.0, b.0, c.0, d.0, e.0, f.0, g.0), expected);
+    #[test]
+    fn initialize() {
+        assert_eq!(
+            cathode(|display| display.initialize().unwrap()),
+            (0, 0, 0, 0, 0, 0, 0)
+        );
+        assert_eq!(
+            anode(|display| display.initialize().unwrap()),
+            (0, 0, 0, 0, 0, 0, 0)
+        );
     }
 
     #[test]
-    fn digit_0() {
-        test_digit(0, (1, 1, 1, 1, 1, 1, 0));
+    fn set_value() {
+        for value in 0..=MAX_VALUE {
+            let expected = cathode(|display| display.set(value).unwrap());
+            assert_eq!(
+                cathode(|display| display.set_value(usize::from(value)).unwrap()),
+                expected
+            );
+            assert_eq!(
+                cathode(|display| display.set_value(u16::from(value)).unwrap()),
+                expected
+            );
+        }
     }
 
     #[test]
-    fn digit_1() {
-        test_digit(1, (0, 1, 1, 0, 0, 0, 0));
+    #[cfg_attr(feature = "strict", should_panic)]
+    fn set_value_truncation() {
+        assert_eq!(
+            cathode(|display| display.set_value(0x101u16).unwrap()),
+            (0, 0, 0, 0, 0, 0, 0)
+        );
     }
 
     #[test]
-    fn digit_2() {
-        test_digit(2, (1, 1, 0, 1, 1, 0, 1));
+    fn map_pins() {
+        use super::Segment;
+
+        struct Logged<'a>(&'a mut TestPin, Segment, &'a core::cell::Cell<u8>);
+
+        impl super::OutputPin for Logged<'_> {
+            type Error = core::convert::Infallible;
+
+            fn set_high(&mut self) -> Result<(), Self::Error> {
+                self.2.set(self.2.get() | 1 << self.1 as u8);
+                self.0 .0 = 1;
+                Ok(())
+            }
+
+            fn set_low(&mut self) -> Result<(), Self::Error> {
+                self.0 .0 = 0;
+                Ok(())
+            }
+        }
+
+        let high = core::cell::Cell::new(0);
+        let levels = levels(|pins| {
+            let mut display = pins
+                .with_common_cathode()
+                .map_pins(|segment, pin| Logged(pin, segment, &high));
+            display.set(7).unwrap();
+            assert_eq!(display.current(), Some(7));
+        });
+        assert_eq!(high.get(), 0b0000111);
+        assert_eq!(levels, [1, 1, 1, 0, 0, 0, 0]);
     }
 
     #[test]
-    fn digit_3() {
-        test_digit(3, (1, 1, 1, 1, 0, 0, 1));
+    fn inverted_segments() {
+        use super::{Segment, Segments};
+
+        let inverted = Segments::from_bits(0b1000000);
+        let levels_1 = levels(|pins| {
+            let mut seven_segment = pins.with_common_cathode().with_inverted_segments(inverted);
+            seven_segment.set(1).unwrap();
+        });
+        assert_eq!(levels_1, [0, 1, 1, 0, 0, 0, 1]);
+        let levels_8 = levels(|pins| {
+            let mut seven_segment = pins.with_common_cathode().with_inverted_segments(inverted);
+            seven_segment.set_ordered(8).unwrap();
+            seven_segment.set_one(Segment::B, false).unwrap();
+        });
+        assert_eq!(levels_8, [1, 0, 1, 1, 1, 1, 0]);
     }
 
     #[test]
-    fn digit_4() {
-        test_digit(4, (0, 1, 1, 0, 0, 1, 1));
+    fn try_into_pins() {
+        use super::{PinError, Segment};
+
+        let display = super::SevenSegmentPins {
+            a: StuckPin(false),
+            b: StuckPin(false),
+            c: StuckPin(false),
+            d: StuckPin(false),
+            e: StuckPin(false),
+            f: StuckPin(false),
+            g: StuckPin(false),
+        }
+        .with_common_cathode()
+        .with_common_pin(StuckPin(true));
+        let (display, error) = display.try_into_pins().map(drop).unwrap_err();
+        assert_eq!(
+            error,
+            PinError {
+                segment: Segment::Common,
+                source: "stuck",
+            }
+        );
+
+        let (display, common) = display.release_common_pin();
+        let (pins, _, _) = display.try_into_pins().map_err(|(_, error)| error).unwrap();
+        assert!(!pins.a.0);
+        assert!(common.0);
     }
 
     #[test]
-    fn digit_5() {
-        test_digit(5, (1, 0, 1, 1, 0, 1, 1));
+    fn encode() {
+        for value in 0..=MAX_VALUE {
+            assert_eq!(
+                cathode(|display| {
+                    let digit = display.encode(value);
+                    display.show(&digit).unwrap();
+                    assert_eq!(
+                        display.current(),
+                        if value <= 0xf { Some(value) } else { None }
+                    );
+                }),
+                cathode(|display| display.set(value).unwrap())
+            );
+            assert_eq!(
+                anode(|display| {
+                    let digit = display.encode(value);
+                    display.show(&digit).unwrap();
+                }),
+                anode(|display| display.set(value).unwrap())
+            );
+        }
     }
 
     #[test]
-    fn digit_6() {
-        test_digit(6, (1, 0, 1, 1, 1, 1, 1));
+    fn from_array() {
+        use super::PolarityKind;
+
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        {
+            let [a, b, c, d, e, f, g] = &mut pins;
+            let mut display = super::erased::from_array([a, b, c, d, e, f, g], PolarityKind::Anode);
+            display.set(1).unwrap();
+        }
+        let levels: [u8; 7] = core::array::from_fn(|i| pins[i].0);
+        assert_eq!(levels, [1, 0, 0, 1, 1, 1, 1]);
     }
 
     #[test]
-    fn digit_7() {
-        test_digit(7, (1, 1, 1, 0, 0, 0, 0));
+    fn common_active_level() {
+        use super::{CommonAnodeActiveLow, CommonCathodeActiveHigh, StaticPolarity};
+        use crate::mock::{MockDisplay, MockPin};
+
+        /// Returns the levels of the common pin and segment `a` when showing 8.
+        fn levels<Common: StaticPolarity>() -> (Option<bool>, Option<bool>) {
+            let mut display = MockDisplay::<Common>::new()
+                .into_inner()
+                .with_common_pin(MockPin::default());
+            display.set(8).unwrap();
+            (display.common_pin.is_high(), display.a.is_high())
+        }
+
+        assert_eq!(levels::<super::Anode>(), (Some(true), Some(false)));
+        assert_eq!(levels::<super::Cathode>(), (Some(false), Some(true)));
+        assert_eq!(levels::<CommonAnodeActiveLow>(), (Some(false), Some(false)));
+        assert_eq!(
+            levels::<CommonCathodeActiveHigh>(),
+            (Some(true), Some(true))
+        );
     }
 
     #[test]
-    fn digit_8() {
-        test_digit(8, (1, 1, 1, 1, 1, 1, 1));
+    fn by_ref() {
+        use crate::mock::MockPin;
+
+        let mut pins = super::SevenSegmentPins {
+            a: MockPin::default(),
+            b: MockPin::default(),
+            c: MockPin::default(),
+            d: MockPin::default(),
+            e: MockPin::default(),
+            f: MockPin::default(),
+            g: MockPin::default(),
+        };
+        pins.by_ref().with_common_cathode().set(7).unwrap();
+        assert_eq!(pins.c.is_high(), Some(true));
+        assert_eq!(pins.d.is_high(), Some(false));
+        pins.by_ref().with_common_cathode().blank().unwrap();
+        assert_eq!(pins.c.is_high(), Some(false));
     }
 
     #[test]
-    fn digit_9() {
-        test_digit(9, (1, 1, 1, 1, 0, 1, 1));
+    fn segment_operators() {
+        use super::Segments;
+
+        let c = Segments::A | Segments::F | Segments::E | Segments::D;
+        assert_eq!(c, Segments::from_char('C').unwrap());
+        assert_eq!(c & Segments::HEX_DIGITS[1], Segments::default());
+        assert_eq!(Segments::DIGITS[8] & Segments::G, Segments::MINUS);
+        assert_eq!(!Segments::MINUS, Segments::DIGITS[0]);
+        assert_eq!(Segments::DIGITS[8] ^ Segments::G, Segments::DIGITS[0]);
+
+        let mut segments = Segments::A;
+        segments |= Segments::B;
+        segments &= Segments::B;
+        segments ^= Segments::C;
+        assert_eq!(segments, Segments::B.union(Segments::C));
     }
 
     #[test]
-    fn digit_a() {
-        test_digit(0xa, (1, 1, 1, 0, 1, 1, 1));
+    fn error_glyphs() {
+        assert_eq!(
+            cathode(|display| display.set_error().unwrap()),
+            cathode(|display| display.set_char('E').unwrap())
+        );
+        assert_eq!(
+            anode(|display| display.set_overflow().unwrap()),
+            (1, 0, 0, 1, 0, 0, 1)
+        );
     }
+
     #[test]
-    fn digit_b() {
-        test_digit(0xb, (0, 0, 1, 1, 1, 1, 1));
+    fn set_raw_u8() {
+        use crate::mock::{MockDisplay, MockPin};
+
+        assert_eq!(
+            cathode(|display| display.set_raw_u8(0b1000_0110).unwrap()),
+            cathode(|display| display.set(1).unwrap())
+        );
+        let pins = MockDisplay::<super::Anode>::new().into_inner().into_pins();
+        let mut display = super::SevenSegmentPinsWithDp {
+            a: pins.a,
+            b: pins.b,
+            c: pins.c,
+            d: pins.d,
+            e: pins.e,
+            f: pins.f,
+            g: pins.g,
+            dp: MockPin::default(),
+        }
+        .with_common_anode();
+        display.set_raw_u8(0b1011_1111).unwrap();
+        assert_eq!(display.current(), None);
+        assert_eq!(display.segments(), super::Segments::DIGITS[0]);
+        assert_eq!(display.into_pins().dp.is_high(), Some(false));
     }
 
     #[test]
-    fn digit_c() {
-        test_digit(0xc, (1, 0, 0, 1, 1, 1, 0));
+    fn set_raw_u8_order() {
+        use core::cell::Cell;
+
+        struct Stamped<'a>(&'a mut TestPin, &'a Cell<u8>, u8);
+
+        impl Stamped<'_> {
+            fn stamp(&mut self, level: u8) {
+                self.0 .0 = level;
+                self.1.set(self.1.get() + 1);
+                self.2 = self.1.get();
+            }
+        }
+
+        impl super::OutputPin for Stamped<'_> {
+            type Error = core::convert::Infallible;
+
+            fn set_high(&mut self) -> Result<(), Self::Error> {
+                self.stamp(1);
+                Ok(())
+            }
+
+            fn set_low(&mut self) -> Result<(), Self::Error> {
+                self.stamp(0);
+                Ok(())
+            }
+        }
+
+        let clock = Cell::new(0);
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let [a, b, c, d, e, f, g, dp, common] = &mut pins;
+        let mut display = super::SevenSegmentPinsWithDp {
+            a: Stamped(a, &clock, 0),
+            b: Stamped(b, &clock, 0),
+            c: Stamped(c, &clock, 0),
+            d: Stamped(d, &clock, 0),
+            e: Stamped(e, &clock, 0),
+            f: Stamped(f, &clock, 0),
+            g: Stamped(g, &clock, 0),
+            dp: Stamped(dp, &clock, 0),
+        }
+        .with_common_cathode()
+        .with_common_pin(Stamped(common, &clock, 0));
+
+        display.set_raw_u8(0b1000_0110).unwrap();
+        assert_eq!(display.dp.0 .0, 1);
+        assert_eq!(display.common_pin.0 .0, 0);
+        assert!(display.g.2 < display.dp.2);
+        assert!(display.dp.2 < display.common_pin.2);
+
+        display.freeze();
+        let written = clock.get();
+        display.set_raw_u8(0b0011_1111).unwrap();
+        assert_eq!(clock.get(), written);
+        assert_eq!(display.dp.0 .0, 1);
+        assert_eq!(display.segments(), super::Segments::DIGITS[0]);
+        display.thaw().unwrap();
+        assert_eq!(display.dp.0 .0, 0);
+        assert_eq!(display.a.0 .0, 1);
     }
 
     #[test]
-    fn digit_d() {
-        test_digit(0xd, (0, 1, 1, 1, 1, 0, 1));
+    fn aux() {
+        use crate::mock::{MockDisplay, MockPin};
+
+        let mut display = MockDisplay::<crate::Anode>::new()
+            .into_inner()
+            .with_aux([MockPin::default(); 2]);
+        display.set_aux(1, true).unwrap();
+        display.set(8).unwrap();
+        display.blank().unwrap();
+        assert_eq!(display.aux[0].is_high(), None);
+        assert_eq!(display.aux[1].is_high(), Some(false));
+        display.set_aux(1, false).unwrap();
+        assert_eq!(display.aux[1].is_high(), Some(true));
+        assert_eq!(
+            display.set_aux(2, true),
+            Err(super::SetError::Invalid(super::InvalidAuxIndex(2)))
+        );
     }
 
     #[test]
-    fn digit_e() {
-        test_digit(0xe, (1, 0, 0, 1, 1, 1, 1));
+    fn set_bits() {
+        let bits = [true, false, false, true, true, false, true];
+        assert_eq!(
+            cathode(|display| display.set_bits(bits).unwrap()),
+            (1, 0, 0, 1, 1, 0, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_bits(bits).unwrap()),
+            (1, 0, 0, 1, 1, 0, 1)
+        );
     }
 
     #[test]
-    fn digit_f() {
-        test_digit(0xf, (1, 0, 0, 0, 1, 1, 1));
+    fn set_one() {
+        use super::Segment;
+
+        assert_eq!(
+            cathode(|display| {
+                display.set(1).unwrap();
+                display.set_one(Segment::G, true).unwrap();
+                display.set_one(Segment::B, false).unwrap();
+                display.set_one(Segment::Common, false).unwrap();
+                assert_eq!(display.current(), None);
+                assert!(display.segments().g);
+                assert!(!display.segments().b);
+            }),
+            (0, 0, 1, 0, 0, 0, 1)
+        );
+        assert_eq!(
+            anode(|display| display.set_one(Segment::D, true).unwrap()),
+            (2, 2, 2, 1, 2, 2, 2)
+        );
     }
 
     #[test]
-    fn digit_invalid() {
-        test_digit(0x10, (0, 0, 0, 0, 0, 0, 0));
+    fn set_one_common_pin() {
+        use super::Segment;
+        use crate::mock::{MockDisplay, MockPin};
+
+        let mut display = MockDisplay::<super::Cathode>::new()
+            .into_inner()
+            .with_common_pin(MockPin::default());
+        display.blank().unwrap();
+        assert_eq!(display.common_pin.is_high(), Some(true));
+        display.set_one(Segment::G, true).unwrap();
+        assert_eq!(display.common_pin.is_high(), Some(false));
+        assert_eq!(display.g.is_high(), Some(true));
+        display.set_one(Segment::G, false).unwrap();
+        assert_eq!(display.common_pin.is_high(), Some(true));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use super::Segments;
+        use serde_test::Token;
+
+        serde_test::assert_tokens(&Segments::MINUS, &[Token::U8(0x40)]);
+        serde_test::assert_tokens(&Segments::DIGITS[7], &[Token::U8(0b0000111)]);
+        serde_test::assert_de_tokens(&Segments::MINUS, &[Token::U8(0xc0)]);
     }
 }
@@ -0,0 +1,103 @@
+//! Scrolling text over multiple digits
+
+use super::Segments;
+
+/// Scrolls a message over `N` digits.
+///
+/// The message is repeated forever, optionally separated by blank digits. Each `frame` can be
+/// shown using `MultiplexedDisplay::show_segments` (or on separate displays) and `advance` moves
+/// the message one digit to the left.
+///
+/// To scroll text convert the characters using `Segments::from_char` first.
+pub struct Marquee<'a, const N: usize> {
+    message: &'a [Segments],
+    gap: usize,
+    offset: usize,
+}
+
+impl<'a, const N: usize> Marquee<'a, N> {
+    /// Creates the marquee showing the beginning of the message.
+    ///
+    /// There's no gap between repetitions of the message.
+    pub fn new(message: &'a [Segments]) -> Self {
+        Marquee {
+            message,
+            gap: 0,
+            offset: 0,
+        }
+    }
+
+    /// Inserts `gap` blank digits between repetitions of the message.
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Returns the segments of all digits in the current frame.
+    pub fn frame(&self) -> [Segments; N] {
+        let len = self.len();
+        let mut frame = [Segments::default(); N];
+        if len == 0 {
+            return frame;
+        }
+
+        for (i, digit) in frame.iter_mut().enumerate() {
+            let position = (self.offset + i) % len;
+            if let Some(segments) = self.message.get(position) {
+                *digit = *segments;
+            }
+        }
+        frame
+    }
+
+    /// Moves the message one digit to the left.
+    pub fn advance(&mut self) {
+        let len = self.len();
+        if len != 0 {
+            self.offset = (self.offset + 1) % len;
+        }
+    }
+
+    /// Length of the message including the gap.
+    fn len(&self) -> usize {
+        self.message.len() + self.gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Marquee;
+    use crate::Segments;
+
+    #[test]
+    fn scroll() {
+        let blank = Segments::default();
+        let message = [
+            Segments::DIGITS[1],
+            Segments::DIGITS[2],
+            Segments::DIGITS[3],
+        ];
+        let mut marquee = Marquee::<'_, 2>::new(&message).with_gap(1);
+        assert_eq!(marquee.frame(), [message[0], message[1]]);
+        marquee.advance();
+        assert_eq!(marquee.frame(), [message[1], message[2]]);
+        marquee.advance();
+        assert_eq!(marquee.frame(), [message[2], blank]);
+        marquee.advance();
+        assert_eq!(marquee.frame(), [blank, message[0]]);
+        marquee.advance();
+        assert_eq!(marquee.frame(), [message[0], message[1]]);
+    }
+
+    #[test]
+    fn longer_than_message() {
+        let message = [Segments::DIGITS[1]];
+        let mut marquee = Marquee::<'_, 3>::new(&message);
+        assert_eq!(marquee.frame(), [message[0]; 3]);
+        marquee.advance();
+        assert_eq!(marquee.frame(), [message[0]; 3]);
+
+        let marquee = Marquee::<'_, 3>::new(&[]);
+        assert_eq!(marquee.frame(), [Segments::default(); 3]);
+    }
+}
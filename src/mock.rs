@@ -0,0 +1,149 @@
+//! In-memory display for testing code using the driver
+//!
+//! `MockDisplay` is a `SevenSegment` backed by `MockPin`s which only remember the level they were
+//! set to. It dereferences to `SevenSegment` so you can pass it to the code under test and then
+//! check which segments are actually lit using `segments`.
+
+use super::{Polarity, SevenSegment, SevenSegmentPins, StaticPolarity};
+use core::convert::Infallible;
+
+/// Pin remembering the level it was set to
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct MockPin {
+    level: Option<bool>,
+}
+
+impl MockPin {
+    /// Returns `true` if the pin is high, `None` if it was never set.
+    pub fn is_high(&self) -> Option<bool> {
+        self.level
+    }
+}
+
+impl super::OutputPin for MockPin {
+    type Error = Infallible;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.level = Some(true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.level = Some(false);
+        Ok(())
+    }
+}
+
+/// The display driven by `MockPin`s
+pub type MockSevenSegment<Common> =
+    SevenSegment<MockPin, MockPin, MockPin, MockPin, MockPin, MockPin, MockPin, Common>;
+
+/// Display backed by in-memory pins
+///
+/// ```
+/// use seven_segment::mock::MockDisplay;
+/// use seven_segment::Cathode;
+///
+/// let mut display = MockDisplay::<Cathode>::new();
+/// display.set(1).unwrap();
+/// assert_eq!(display.segments(), [false, true, true, false, false, false, false]);
+/// ```
+pub struct MockDisplay<Common>(MockSevenSegment<Common>);
+
+impl<Common: StaticPolarity> MockDisplay<Common> {
+    /// Creates the display with all pins unset.
+    pub fn new() -> Self {
+        let pins = SevenSegmentPins {
+            a: MockPin::default(),
+            b: MockPin::default(),
+            c: MockPin::default(),
+            d: MockPin::default(),
+            e: MockPin::default(),
+            f: MockPin::default(),
+            g: MockPin::default(),
+        };
+        MockDisplay(pins.with_common())
+    }
+}
+
+impl<Common: StaticPolarity> Default for MockDisplay<Common> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Common: Polarity> MockDisplay<Common> {
+    /// Returns which segments are lit according to the levels of the pins.
+    ///
+    /// Unlike `SevenSegment::segments` this reads the pins rather than the stored state so it
+    /// catches bugs in polarity handling too. Pins that were never set are considered unlit.
+    pub fn segments(&self) -> [bool; 7] {
+        let cathode = Common::is_cathode(self.0.state.polarity);
+        let lit = |pin: &MockPin| pin.level == Some(cathode);
+        [
+            lit(&self.0.a),
+            lit(&self.0.b),
+            lit(&self.0.c),
+            lit(&self.0.d),
+            lit(&self.0.e),
+            lit(&self.0.f),
+            lit(&self.0.g),
+        ]
+    }
+
+    /// Returns the levels of the pins, `None` for pins that were never set.
+    pub fn levels(&self) -> [Option<bool>; 7] {
+        [
+            self.0.a.level,
+            self.0.b.level,
+            self.0.c.level,
+            self.0.d.level,
+            self.0.e.level,
+            self.0.f.level,
+            self.0.g.level,
+        ]
+    }
+
+    /// Returns the wrapped display.
+    pub fn into_inner(self) -> MockSevenSegment<Common> {
+        self.0
+    }
+}
+
+impl<Common> core::ops::Deref for MockDisplay<Common> {
+    type Target = MockSevenSegment<Common>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<Common> core::ops::DerefMut for MockDisplay<Common> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockDisplay;
+    use crate::{Anode, Cathode};
+
+    #[test]
+    fn polarity() {
+        let mut cathode = MockDisplay::<Cathode>::new();
+        let mut anode = MockDisplay::<Anode>::new();
+        assert_eq!(cathode.segments(), [false; 7]);
+        assert_eq!(cathode.levels(), [None; 7]);
+
+        cathode.set(8).unwrap();
+        anode.set(8).unwrap();
+        assert_eq!(cathode.segments(), [true; 7]);
+        assert_eq!(anode.segments(), [true; 7]);
+        assert_eq!(cathode.levels(), [Some(true); 7]);
+        assert_eq!(anode.levels(), [Some(false); 7]);
+
+        anode.blank().unwrap();
+        assert_eq!(anode.segments(), [false; 7]);
+    }
+}
@@ -0,0 +1,518 @@
+//! Multiplexed multi-digit displays
+//!
+//! Multi-digit displays often share the segment lines between all digits and have one common pin
+//! per digit instead. Only one digit is lit at a time and the digits are quickly cycled through so
+//! that all of them appear to be lit.
+
+use super::number::{render_unsigned, OVER_RANGE};
+use super::{
+    write_common, OptionalPin, OutputPin, PinError, Polarity, Segment, SegmentSink, Segments,
+    SevenSegment,
+};
+
+/// Display of `N` digits sharing the segment pins.
+///
+/// `S` is the `SevenSegment` driving the shared segment pins and `P` is the type of common pins,
+/// one for each digit. The common pins are driven according to the polarity of the display: for
+/// `Anode` the digit is enabled by setting its common pin high, for `Cathode` by setting it low.
+/// Use `CommonAnodeActiveLow` or `CommonCathodeActiveHigh` if the commons are switched by
+/// transistors inverting the level.
+///
+/// Call `show` to change the shown digits and `refresh` periodically (e.g. from a timer interrupt)
+/// to actually show them. Each call of `refresh` shows the next digit so you're in full control
+/// of the timing.
+pub struct MultiplexedDisplay<S, P, const N: usize> {
+    segments: S,
+    commons: [P; N],
+    buffer: [Segments; N],
+    brightness: [u8; N],
+    pwm_phase: u8,
+    frame_rate: u16,
+    position: usize,
+}
+
+impl<S, P, const N: usize> MultiplexedDisplay<S, P, N> {
+    /// Creates the display from the shared segments and common pins.
+    ///
+    /// The first digit is at index 0. All digits are initially blank.
+    pub fn new(segments: S, commons: [P; N]) -> Self {
+        MultiplexedDisplay {
+            segments,
+            commons,
+            buffer: [Segments::default(); N],
+            brightness: [u8::MAX; N],
+            pwm_phase: 0,
+            frame_rate: 100,
+            position: N.saturating_sub(1),
+        }
+    }
+
+    /// Changes the shown segments of all digits.
+    pub fn show_segments(&mut self, segments: [Segments; N]) {
+        self.buffer = segments;
+    }
+
+    /// Sets the brightness of the digit at `position`.
+    ///
+    /// 0 means the digit is always off, 255 (the default) means it's enabled in each scan, the
+    /// brightness is linear in between. The digit is enabled in `level` of each 255 scans so the
+    /// display must be scanned fast enough to avoid visible flicker when dimming.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `position` is out of range.
+    pub fn set_digit_brightness(&mut self, position: usize, level: u8) {
+        self.brightness[position] = level;
+    }
+
+    /// Sets how many times per second all digits should be shown, 100 by default.
+    ///
+    /// This is only used to compute `digit_period_us`, you're still in control of the timing.
+    /// The rate of 0 is treated as 1.
+    pub fn set_frame_rate(&mut self, hz: u16) {
+        self.frame_rate = hz.max(1);
+    }
+
+    /// Returns how many microseconds to wait between calls of `refresh` to reach the frame rate.
+    ///
+    /// Returns 0 if there are no digits.
+    pub fn digit_period_us(&self) -> u32 {
+        match u32::from(self.frame_rate).checked_mul(N as u32) {
+            Some(0) => 0,
+            Some(calls) => 1_000_000 / calls,
+            None => 0,
+        }
+    }
+
+    /// Returns the segments that are being shown on digit positions.
+    pub fn buffer(&self) -> &[Segments; N] {
+        &self.buffer
+    }
+
+    /// Returns the position of the digit that is currently lit.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the segment display and the common pins.
+    pub fn into_parts(self) -> (S, [P; N]) {
+        (self.segments, self.commons)
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux, P, const N: usize>
+    MultiplexedDisplay<
+        SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>,
+        P,
+        N,
+    >
+{
+    /// Changes the shown values.
+    ///
+    /// The values are shown the same way `SevenSegment::set` of the segment display shows them,
+    /// so the glyphs, the value range and the invalid glyph chosen for it apply. The change
+    /// becomes visible as `refresh` is called.
+    ///
+    /// # Panics
+    ///
+    /// If the `strict` feature is enabled invalid values panic just like in `SevenSegment::set`.
+    #[track_caller]
+    pub fn show(&mut self, digits: [u8; N]) {
+        for (segments, digit) in self.buffer.iter_mut().zip(&digits) {
+            *segments = self.segments.checked_glyph(*digit);
+        }
+    }
+
+    /// Changes the shown values from a slice.
+    ///
+    /// The first `N` values are shown the same way as in `show`, so whether values 10-15 are
+    /// shown as hexadecimal digits or blank depends on the value range of the segment display.
+    /// The digits beyond the end of a shorter slice are blank.
+    #[track_caller]
+    pub fn show_slice(&mut self, digits: &[u8]) {
+        for (i, segments) in self.buffer.iter_mut().enumerate() {
+            *segments = match digits.get(i) {
+                Some(digit) => self.segments.checked_glyph(*digit),
+                None => Segments::default(),
+            };
+        }
+    }
+
+    /// Shows the number with leading zeros blank.
+    ///
+    /// The digits are shown the same way as in `show`, so the glyphs of the segment display apply.
+    /// Returns `false` if the number doesn't fit, the top segments of all digits are then lit. See
+    /// `number::render_unsigned` for details.
+    pub fn show_number(&mut self, value: u32, keep_zero: bool) -> bool {
+        let mut digits = [None; N];
+        let fits = render_unsigned(value, keep_zero, &mut digits);
+        for (segments, digit) in self.buffer.iter_mut().zip(&digits) {
+            *segments = match digit {
+                Some(digit) => self.segments.checked_glyph(*digit),
+                None if fits => Segments::default(),
+                None => OVER_RANGE,
+            };
+        }
+        fits
+    }
+}
+
+/// Shows the digit or segments on the last position and blanks the others.
+///
+/// Only the buffer is changed so this never fails, the change becomes visible as `refresh` is
+/// called. Digits are shown the same way `SevenSegment::set` of the segment display shows them.
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux, P, const N: usize> SegmentSink
+    for MultiplexedDisplay<
+        SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>,
+        P,
+        N,
+    >
+{
+    type Error = core::convert::Infallible;
+
+    fn set_digit(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.set_segments(self.segments.checked_glyph(value))
+    }
+
+    fn set_segments(&mut self, segments: Segments) -> Result<(), Self::Error> {
+        self.buffer = [Segments::default(); N];
+        if let Some(last) = self.buffer.last_mut() {
+            *last = segments;
+        }
+        Ok(())
+    }
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, P, const N: usize>
+    MultiplexedDisplay<SevenSegment<A, B, C, D, E, F, G, Common, DP>, P, N>
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    P: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+{
+    /// Switches to the next digit.
+    ///
+    /// This disables the currently lit digit, sets the segments of the following one and enables
+    /// it. After the last digit the first one is shown again. Dimmed digits are left disabled in
+    /// some scans, see `set_digit_brightness`.
+    pub fn refresh(&mut self) -> Result<(), PinError<A::Error>> {
+        if N == 0 {
+            return Ok(());
+        }
+
+        let active_low = Common::is_common_active_low(self.segments.state.polarity);
+        write_common(&mut self.commons[self.position], false, active_low)
+            .map_err(PinError::at(Segment::Common))?;
+        self.position = (self.position + 1) % N;
+        if self.position == 0 {
+            self.pwm_phase = if self.pwm_phase >= u8::MAX - 1 {
+                0
+            } else {
+                self.pwm_phase + 1
+            };
+        }
+        if self.pwm_phase >= self.brightness[self.position] {
+            return Ok(());
+        }
+        self.segments.set_segments(self.buffer[self.position])?;
+        write_common(&mut self.commons[self.position], true, active_low)
+            .map_err(PinError::at(Segment::Common))
+    }
+
+    /// Switches to the next digit and returns how many microseconds to wait before the next call.
+    ///
+    /// This is the same as `refresh` followed by `digit_period_us`.
+    pub fn scan_once(&mut self) -> Result<u32, PinError<A::Error>> {
+        self.refresh()?;
+        Ok(self.digit_period_us())
+    }
+
+    /// Keeps refreshing the display, waiting `period_us` microseconds after showing each digit.
+    ///
+    /// This is intended to be run in a dedicated task which owns the display. Before each scan
+    /// of all digits `update` is called with the buffer so that it can copy the new content from
+    /// state shared with other tasks. It never returns unless setting a pin fails.
+    #[cfg(feature = "async")]
+    pub async fn run<Delay, Update>(
+        &mut self,
+        delay: &mut Delay,
+        period_us: u32,
+        mut update: Update,
+    ) -> Result<core::convert::Infallible, PinError<A::Error>>
+    where
+        Delay: embedded_hal_async::delay::DelayNs,
+        Update: FnMut(&mut [Segments; N]),
+    {
+        loop {
+            if self.position + 1 >= N {
+                update(&mut self.buffer);
+            }
+            self.refresh()?;
+            delay.delay_us(period_us).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiplexedDisplay;
+    use crate::tests::TestPin;
+
+    #[test]
+    fn refresh() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let mut commons = [TestPin(2), TestPin(2), TestPin(2)];
+        let [a, b, c, d, e, f, g] = &mut pins;
+        let segments = crate::SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+        .with_common_cathode();
+        let [c0, c1, c2] = &mut commons;
+        let mut display = MultiplexedDisplay::new(segments, [c0, c1, c2]);
+        display.show([1, 2, 0xc]);
+
+        display.refresh().unwrap();
+        assert_eq!(display.position(), 0);
+        assert_eq!(display.commons[0].0, 0);
+        assert_eq!(display.commons[1].0, 2);
+        assert_eq!(display.commons[2].0, 1);
+        assert_eq!(display.segments.b.0, 1);
+        assert_eq!(display.segments.e.0, 0);
+
+        display.refresh().unwrap();
+        assert_eq!(display.position(), 1);
+        assert_eq!(display.commons[0].0, 1);
+        assert_eq!(display.commons[1].0, 0);
+        assert_eq!(display.segments.e.0, 1);
+
+        display.refresh().unwrap();
+        assert_eq!(display.position(), 2);
+        assert_eq!(display.commons[1].0, 1);
+        assert_eq!(display.commons[2].0, 0);
+        assert_eq!(display.segments.b.0, 0);
+
+        assert_eq!(display.scan_once(), Ok(3333));
+        assert_eq!(display.position(), 0);
+        assert_eq!(display.commons[0].0, 0);
+        assert_eq!(display.commons[2].0, 1);
+    }
+
+    #[test]
+    fn digit_brightness() {
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let mut commons = [TestPin(2), TestPin(2)];
+        let [a, b, c, d, e, f, g] = &mut pins;
+        let segments = crate::SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+        .with_common_cathode();
+        let [c0, c1] = &mut commons;
+        let mut display = MultiplexedDisplay::new(segments, [c0, c1]);
+        display.show([8, 8]);
+        display.set_digit_brightness(1, 51);
+
+        let mut enabled = [0; 2];
+        for _ in 0..255 {
+            for count in &mut enabled {
+                display.refresh().unwrap();
+                if display.commons[display.position()].0 == 0 {
+                    *count += 1;
+                }
+            }
+        }
+        assert_eq!(enabled, [255, 51]);
+
+        display.set_digit_brightness(0, 0);
+        display.refresh().unwrap();
+        assert_eq!(display.commons[0].0, 1);
+    }
+
+    #[test]
+    fn digit_period() {
+        let mut display = MultiplexedDisplay::new((), [(); 4]);
+        assert_eq!(display.digit_period_us(), 2500);
+        display.set_frame_rate(60);
+        assert_eq!(display.digit_period_us(), 4166);
+        display.set_frame_rate(0);
+        assert_eq!(display.digit_period_us(), 250_000);
+        assert_eq!(
+            MultiplexedDisplay::<(), (), 0>::new((), []).digit_period_us(),
+            0
+        );
+    }
+
+    #[test]
+    fn show_uses_glyphs() {
+        use crate::mock::MockDisplay;
+        use crate::{Cathode, SegmentSink, Segments, ValueRange};
+
+        let segments = MockDisplay::<Cathode>::new()
+            .into_inner()
+            .with_glyphs([Segments::MINUS; 16])
+            .with_value_range(ValueRange::Decimal);
+        let mut display = MultiplexedDisplay::new(segments, [(); 2]);
+        display.show([3, 9]);
+        assert_eq!(display.buffer(), &[Segments::MINUS; 2]);
+        display.set_digit(1).unwrap();
+        assert_eq!(display.buffer(), &[Segments::default(), Segments::MINUS]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "strict", should_panic)]
+    fn show_invalid() {
+        use crate::mock::MockDisplay;
+        use crate::{Cathode, Segments, ValueRange};
+
+        let segments = MockDisplay::<Cathode>::new()
+            .into_inner()
+            .with_value_range(ValueRange::Decimal);
+        let mut display = MultiplexedDisplay::new(segments, [(); 2]);
+        display.show([1, 0xa]);
+        assert_eq!(
+            display.buffer(),
+            &[Segments::DIGITS[1], Segments::default()]
+        );
+    }
+
+    #[test]
+    fn show_slice() {
+        use crate::mock::MockDisplay;
+        use crate::{Cathode, DigitStyle, Segments, ValueRange};
+
+        let segments = MockDisplay::<Cathode>::new()
+            .into_inner()
+            .with_value_range(ValueRange::Decimal);
+        let mut display = MultiplexedDisplay::new(segments, [(); 3]);
+        display.show_slice(&[4, 2]);
+        let d = Segments::DIGITS;
+        assert_eq!(display.buffer(), &[d[4], d[2], Segments::default()]);
+        display.show_slice(&[1, 2, 3, 4]);
+        assert_eq!(display.buffer(), &[d[1], d[2], d[3]]);
+        if !cfg!(feature = "strict") {
+            display.show_slice(&[0xa, 1]);
+            assert_eq!(
+                display.buffer(),
+                &[Segments::default(), d[1], Segments::default()]
+            );
+        }
+        assert!(display.show_number(42, true));
+        assert_eq!(display.buffer(), &[Segments::default(), d[4], d[2]]);
+        assert!(!display.show_number(1000, true));
+        assert_eq!(display.buffer(), &[Segments::from_bits(1); 3]);
+
+        let segments = MockDisplay::<Cathode>::new()
+            .into_inner()
+            .with_digit_style(DigitStyle::FullTail);
+        let mut display = MultiplexedDisplay::new(segments, [(); 2]);
+        assert!(display.show_number(7, true));
+        let seven = DigitStyle::FullTail.glyphs()[7];
+        assert_ne!(seven, d[7]);
+        assert_eq!(display.buffer(), &[Segments::default(), seven]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn run() {
+        use crate::Segments;
+        use core::future::Future;
+        use core::task::{Context, Poll};
+
+        /// Delay which completes on the second poll so that the test can step through digits.
+        struct YieldDelay(u32);
+
+        impl embedded_hal_async::delay::DelayNs for YieldDelay {
+            async fn delay_ns(&mut self, ns: u32) {
+                self.0 += ns / 1000;
+                let mut yielded = false;
+                core::future::poll_fn(|_| {
+                    if yielded {
+                        Poll::Ready(())
+                    } else {
+                        yielded = true;
+                        Poll::Pending
+                    }
+                })
+                .await
+            }
+        }
+
+        let mut pins = [
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+            TestPin(2),
+        ];
+        let mut commons = [TestPin(2), TestPin(2)];
+        let [a, b, c, d, e, f, g] = &mut pins;
+        let segments = crate::SevenSegmentPins {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        }
+        .with_common_cathode();
+        let [c0, c1] = &mut commons;
+        let mut display = MultiplexedDisplay::new(segments, [c0, c1]);
+        let mut delay = YieldDelay(0);
+        let mut updates = 0;
+        {
+            let future = display.run(&mut delay, 500, |buffer| {
+                updates += 1;
+                *buffer = [Segments::DIGITS[updates], Segments::MINUS];
+            });
+            let mut future = core::pin::pin!(future);
+            let waker = crate::asynch::tests::noop_waker();
+            let mut context = Context::from_waker(&waker);
+            for _ in 0..3 {
+                assert!(future.as_mut().poll(&mut context).is_pending());
+            }
+        }
+        assert_eq!(updates, 2);
+        assert_eq!(delay.0, 1500);
+        assert_eq!(display.position(), 0);
+        assert_eq!(display.buffer()[0], Segments::DIGITS[2]);
+        assert_eq!(display.commons[0].0, 0);
+        assert_eq!(display.commons[1].0, 1);
+    }
+}
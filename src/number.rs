@@ -0,0 +1,219 @@
+//! Helpers for showing numbers on multiple digits
+
+use super::Segments;
+
+/// Splits the value into tens and units.
+///
+/// Returns `None` if the value is above 99 and thus doesn't fit into two digits, so that you can
+/// show an error instead (e.g. `EE`).
+pub const fn bcd(value: u8) -> Option<(u8, u8)> {
+    if value > 99 {
+        None
+    } else {
+        Some((value / 10, value % 10))
+    }
+}
+
+/// The over range marker of positive numbers, the top segment.
+pub const OVER_RANGE: Segments = Segments::from_bits(0b0000001);
+
+/// Position of the decimal point returned by `render_fixed`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DpPos {
+    /// The number is whole, no decimal point should be lit.
+    None,
+    /// The decimal point of the digit at this index should be lit.
+    At(usize),
+    /// The number doesn't fit, the digits show the over range marker.
+    ///
+    /// The marker is the top segment on all digits for positive numbers and the bottom segment
+    /// for negative numbers.
+    OverRange,
+}
+
+/// Renders the fixed-point number into the digits.
+///
+/// The shown number is `value / 10^scale`, e.g. `235` with scale `1` is `23.5`. The digits are
+/// ordered from the most significant one and the number is right-aligned, the unused digits are
+/// blank. Negative numbers are prefixed with the minus sign.
+///
+/// If the number has more fractional digits than can be shown the extra ones are truncated,
+/// possibly all of them. If even the integer part doesn't fit, the over range marker is shown.
+/// Light the decimal point as indicated by the returned value.
+pub fn render_fixed(value: i32, scale: u8, digits: &mut [Segments]) -> DpPos {
+    let negative = value < 0;
+    let magnitude = value.unsigned_abs();
+    let available = digits.len().saturating_sub(negative.into());
+
+    for frac in (0..=scale).rev() {
+        let scaled = 10u32
+            .checked_pow((scale - frac).into())
+            .map_or(0, |divisor| magnitude / divisor);
+        let len = digit_count(scaled).max(usize::from(frac) + 1);
+        if len > available || digits.is_empty() {
+            continue;
+        }
+
+        let start = digits.len() - len;
+        let mut remaining = scaled;
+        for digit in digits[start..].iter_mut().rev() {
+            *digit = Segments::DIGITS[(remaining % 10) as usize];
+            remaining /= 10;
+        }
+        for digit in &mut digits[..start] {
+            *digit = Segments::default();
+        }
+        if negative {
+            digits[start - 1] = Segments::MINUS;
+        }
+
+        return if frac == 0 {
+            DpPos::None
+        } else {
+            DpPos::At(digits.len() - 1 - usize::from(frac))
+        };
+    }
+
+    let marker = if negative {
+        Segments::from_bits(0b0001000)
+    } else {
+        OVER_RANGE
+    };
+    for digit in digits {
+        *digit = marker;
+    }
+    DpPos::OverRange
+}
+
+/// Splits the number into decimal digits with leading zeros blank.
+///
+/// The digits are ordered from the most significant one and the number is right-aligned, blank
+/// digits are `None`. If `keep_zero` is `true` the value 0 is shown as a single `0`, otherwise all
+/// digits are blank. Returns `false` if the number doesn't fit, all digits are then blank and you
+/// should show an error instead, e.g. `OVER_RANGE` like `render_fixed` does.
+pub fn render_unsigned(value: u32, keep_zero: bool, digits: &mut [Option<u8>]) -> bool {
+    for digit in digits.iter_mut() {
+        *digit = None;
+    }
+    if value == 0 && !keep_zero {
+        return true;
+    }
+    let len = digit_count(value);
+    if len > digits.len() {
+        return false;
+    }
+
+    let start = digits.len() - len;
+    let mut remaining = value;
+    for digit in digits[start..].iter_mut().rev() {
+        *digit = Some((remaining % 10) as u8);
+        remaining /= 10;
+    }
+    true
+}
+
+/// Splits the value into hexadecimal digits, e.g. for `SevenSegment::set_hex`.
+///
+/// The digits are ordered from the most significant one and include leading zeros. If there are
+/// fewer than four digits the high nibbles are truncated and `false` is returned if any of them
+/// wasn't zero. If there are more than four digits the leading ones are zero.
+pub fn render_hex(value: u16, digits: &mut [u8]) -> bool {
+    let mut remaining = u32::from(value);
+    for digit in digits.iter_mut().rev() {
+        *digit = (remaining & 0xf) as u8;
+        remaining >>= 4;
+    }
+    remaining == 0
+}
+
+/// Returns the number of decimal digits needed to show the value, at least 1.
+fn digit_count(mut value: u32) -> usize {
+    let mut count = 1;
+    while value >= 10 {
+        value /= 10;
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn bcd() {
+        assert_eq!(super::bcd(0), Some((0, 0)));
+        assert_eq!(super::bcd(7), Some((0, 7)));
+        assert_eq!(super::bcd(42), Some((4, 2)));
+        assert_eq!(super::bcd(99), Some((9, 9)));
+        assert_eq!(super::bcd(100), None);
+        assert_eq!(super::bcd(255), None);
+    }
+
+    #[test]
+    fn render_unsigned() {
+        use super::render_unsigned;
+
+        let mut digits = [Some(0xff); 4];
+        assert!(render_unsigned(7, true, &mut digits));
+        assert_eq!(digits, [None, None, None, Some(7)]);
+        assert!(render_unsigned(0, true, &mut digits));
+        assert_eq!(digits, [None, None, None, Some(0)]);
+        assert!(render_unsigned(0, false, &mut digits));
+        assert_eq!(digits, [None; 4]);
+        assert!(render_unsigned(9001, false, &mut digits));
+        assert_eq!(digits, [Some(9), Some(0), Some(0), Some(1)]);
+        assert!(!render_unsigned(10000, true, &mut digits));
+        assert_eq!(digits, [None; 4]);
+        assert!(!render_unsigned(0, true, &mut []));
+        assert!(render_unsigned(0, false, &mut []));
+    }
+
+    #[test]
+    fn render_hex() {
+        use super::render_hex;
+
+        let mut digits = [0xff; 4];
+        assert!(render_hex(0xbeef, &mut digits));
+        assert_eq!(digits, [0xb, 0xe, 0xe, 0xf]);
+        assert!(render_hex(0x2a, &mut digits));
+        assert_eq!(digits, [0, 0, 2, 0xa]);
+        let mut digits = [0xff; 2];
+        assert!(render_hex(0x2a, &mut digits));
+        assert!(!render_hex(0x12a, &mut digits));
+        assert_eq!(digits, [2, 0xa]);
+        let mut digits = [0xff; 5];
+        assert!(render_hex(u16::MAX, &mut digits));
+        assert_eq!(digits, [0, 0xf, 0xf, 0xf, 0xf]);
+    }
+
+    #[test]
+    fn render_fixed() {
+        use super::{render_fixed, DpPos};
+        use crate::Segments;
+
+        let blank = Segments::default();
+        let d = Segments::DIGITS;
+        let mut digits = [blank; 3];
+        assert_eq!(render_fixed(235, 1, &mut digits), DpPos::At(1));
+        assert_eq!(digits, [d[2], d[3], d[5]]);
+        assert_eq!(render_fixed(-5, 1, &mut digits), DpPos::At(1));
+        assert_eq!(digits, [Segments::MINUS, d[0], d[5]]);
+        assert_eq!(render_fixed(7, 0, &mut digits), DpPos::None);
+        assert_eq!(digits, [blank, blank, d[7]]);
+        assert_eq!(render_fixed(-42, 0, &mut digits), DpPos::None);
+        assert_eq!(digits, [Segments::MINUS, d[4], d[2]]);
+        assert_eq!(render_fixed(12345, 3, &mut digits), DpPos::At(1));
+        assert_eq!(digits, [d[1], d[2], d[3]]);
+        assert_eq!(render_fixed(12345, 1, &mut digits), DpPos::OverRange);
+        assert_eq!(digits, [Segments::from_bits(1); 3]);
+        assert_eq!(render_fixed(-1000, 0, &mut digits), DpPos::OverRange);
+        assert_eq!(digits, [Segments::from_bits(0b1000); 3]);
+        assert_eq!(render_fixed(i32::MIN, 20, &mut digits), DpPos::At(1));
+        assert_eq!(digits, [Segments::MINUS, d[0], d[0]]);
+
+        let mut digits = [blank; 2];
+        assert_eq!(render_fixed(235, 1, &mut digits), DpPos::None);
+        assert_eq!(digits, [d[2], d[3]]);
+        assert_eq!(render_fixed(0, 0, &mut []), DpPos::OverRange);
+    }
+}
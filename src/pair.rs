@@ -0,0 +1,128 @@
+//! Two separate displays showing one number
+
+use super::number::bcd;
+use super::{OptionalPin, OutputPin, PinError, Polarity, SevenSegment};
+
+/// Pair of single-digit displays showing a two-digit number.
+///
+/// This is intended for displays which are not multiplexed, so each digit has its own pins.
+/// Setting the value through the pair avoids forgetting to update one of the digits.
+pub struct DisplayPair<Tens, Units> {
+    tens: Tens,
+    units: Units,
+}
+
+impl<Tens, Units> DisplayPair<Tens, Units> {
+    /// Creates the pair from the display showing tens and the one showing units.
+    pub fn new(tens: Tens, units: Units) -> Self {
+        DisplayPair { tens, units }
+    }
+
+    /// Returns the display showing tens.
+    pub fn tens(&mut self) -> &mut Tens {
+        &mut self.tens
+    }
+
+    /// Returns the display showing units.
+    pub fn units(&mut self) -> &mut Units {
+        &mut self.units
+    }
+
+    /// Deconstructs the pair and returns the displays showing tens and units.
+    pub fn into_inner(self) -> (Tens, Units) {
+        (self.tens, self.units)
+    }
+}
+
+impl<
+        A,
+        B,
+        C,
+        D,
+        E,
+        F,
+        G,
+        Common,
+        DP,
+        CommonPin,
+        A2,
+        B2,
+        C2,
+        D2,
+        E2,
+        F2,
+        G2,
+        Common2,
+        DP2,
+        CommonPin2,
+    >
+    DisplayPair<
+        SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin>,
+        SevenSegment<A2, B2, C2, D2, E2, F2, G2, Common2, DP2, CommonPin2>,
+    >
+where
+    A: OutputPin,
+    B: OutputPin<Error = A::Error>,
+    C: OutputPin<Error = A::Error>,
+    D: OutputPin<Error = A::Error>,
+    E: OutputPin<Error = A::Error>,
+    F: OutputPin<Error = A::Error>,
+    G: OutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    A2: OutputPin<Error = A::Error>,
+    B2: OutputPin<Error = A::Error>,
+    C2: OutputPin<Error = A::Error>,
+    D2: OutputPin<Error = A::Error>,
+    E2: OutputPin<Error = A::Error>,
+    F2: OutputPin<Error = A::Error>,
+    G2: OutputPin<Error = A::Error>,
+    Common2: Polarity,
+    DP2: OptionalPin<A::Error>,
+    CommonPin2: OptionalPin<A::Error>,
+{
+    /// Shows the value on both displays.
+    ///
+    /// The valid values are 0-99, values below 10 are shown with a leading zero. In case of
+    /// invalid value both displays will be blank.
+    pub fn set(&mut self, value: u8) -> Result<(), PinError<A::Error>> {
+        match bcd(value) {
+            Some((tens, units)) => {
+                self.tens.set(tens)?;
+                self.units.set(units)
+            }
+            None => self.blank(),
+        }
+    }
+
+    /// Turns off all segments of both displays.
+    pub fn blank(&mut self) -> Result<(), PinError<A::Error>> {
+        self.tens.blank()?;
+        self.units.blank()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisplayPair;
+    use crate::mock::MockDisplay;
+    use crate::{Anode, Cathode};
+
+    #[test]
+    fn set() {
+        let tens = MockDisplay::<Cathode>::new().into_inner();
+        let units = MockDisplay::<Anode>::new().into_inner();
+        let mut pair = DisplayPair::new(tens, units);
+
+        pair.set(42).unwrap();
+        assert_eq!(pair.tens().current(), Some(4));
+        assert_eq!(pair.units().current(), Some(2));
+        pair.set(7).unwrap();
+        assert_eq!(pair.tens().current(), Some(0));
+        assert_eq!(pair.units().current(), Some(7));
+        pair.set(100).unwrap();
+        assert_eq!(pair.tens().current(), None);
+        assert_eq!(pair.units().current(), None);
+    }
+}
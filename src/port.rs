@@ -0,0 +1,298 @@
+//! Displays connected to a whole port, e.g. an I2C expander
+//!
+//! Expanders such as PCF8574 can set all eight pins in a single transaction. Driving them through
+//! per-pin `OutputPin` adapters needs a transaction for each segment, `PortSevenSegment` writes
+//! all of them at once instead. Shift registers such as 74HC595 are supported by `ShiftRegister`.
+
+use super::{
+    DynamicPolarity, InvalidDigit, OutputPin, Polarity, PolarityKind, SegmentSink, Segments,
+    SetError, StaticPolarity,
+};
+use embedded_hal::blocking::spi;
+
+/// Writes all pins of the port at once.
+///
+/// Bits 0 to 6 drive segments `a` to `g`, bit 7 drives the decimal point. A set bit means the
+/// pin is high. If your display is wired differently remap the bits in the implementation.
+pub trait PortWriter {
+    /// Error returned when writing fails
+    type Error;
+
+    /// Sets the levels of all pins.
+    fn write(&mut self, levels: u8) -> Result<(), Self::Error>;
+}
+
+impl<W: PortWriter + ?Sized> PortWriter for &mut W {
+    type Error = W::Error;
+
+    fn write(&mut self, levels: u8) -> Result<(), Self::Error> {
+        (**self).write(levels)
+    }
+}
+
+/// Shift register, such as 74HC595, driven by SPI.
+///
+/// The byte is sent over SPI and then the latch pin (`RCLK` on 74HC595) is pulsed high to update
+/// the outputs. With the usual MSB-first SPI mode segment `a` ends up on output `QA`, `g` on `QG`
+/// and the decimal point on `QH`. Each write sends a single byte so for a chain of registers
+/// driving multiple digits implement `PortWriter` that sends the bytes of all digits instead.
+pub struct ShiftRegister<SPI, Latch> {
+    spi: SPI,
+    latch: Latch,
+}
+
+impl<SPI, Latch> ShiftRegister<SPI, Latch> {
+    /// Creates the shift register from the SPI bus and the latch pin.
+    pub fn new(spi: SPI, latch: Latch) -> Self {
+        ShiftRegister { spi, latch }
+    }
+
+    /// Returns the SPI bus and the latch pin.
+    pub fn into_parts(self) -> (SPI, Latch) {
+        (self.spi, self.latch)
+    }
+}
+
+impl<SPI: spi::Write<u8>, Latch: OutputPin> PortWriter for ShiftRegister<SPI, Latch> {
+    type Error = ShiftRegisterError<SPI::Error, Latch::Error>;
+
+    fn write(&mut self, levels: u8) -> Result<(), Self::Error> {
+        self.latch.set_low().map_err(ShiftRegisterError::Latch)?;
+        self.spi.write(&[levels]).map_err(ShiftRegisterError::Spi)?;
+        self.latch.set_high().map_err(ShiftRegisterError::Latch)
+    }
+}
+
+/// Error returned when writing to the shift register fails.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ShiftRegisterError<S, L> {
+    /// Sending the byte failed.
+    Spi(S),
+    /// Setting the latch pin failed.
+    Latch(L),
+}
+
+impl<S: core::fmt::Display, L: core::fmt::Display> core::fmt::Display for ShiftRegisterError<S, L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ShiftRegisterError::Spi(error) => write!(f, "failed to send the byte: {}", error),
+            ShiftRegisterError::Latch(error) => write!(f, "failed to set the latch pin: {}", error),
+        }
+    }
+}
+
+/// 7-segment display connected to a shift register
+pub type ShiftRegisterSevenSegment<SPI, Latch, Common> =
+    PortSevenSegment<ShiftRegister<SPI, Latch>, Common>;
+
+/// 7-segment display connected to a port.
+///
+/// This provides the same basic methods as `SevenSegment` but each of them writes the port only
+/// once.
+pub struct PortSevenSegment<W, Common> {
+    common: core::marker::PhantomData<fn() -> Common>,
+    polarity: PolarityKind,
+    port: W,
+    value: Option<u8>,
+    segments: Segments,
+    dp: bool,
+}
+
+impl<W, Common: StaticPolarity> PortSevenSegment<W, Common> {
+    /// Constructs the display with polarity known at compile time.
+    ///
+    /// The port is not written until the display is set.
+    pub fn new(port: W) -> Self {
+        Self::with_polarity_kind(port, Common::KIND)
+    }
+}
+
+impl<W> PortSevenSegment<W, DynamicPolarity> {
+    /// Constructs the display with polarity chosen at runtime.
+    pub fn with_polarity(port: W, polarity: PolarityKind) -> Self {
+        Self::with_polarity_kind(port, polarity)
+    }
+}
+
+impl<W, Common> PortSevenSegment<W, Common> {
+    fn with_polarity_kind(port: W, polarity: PolarityKind) -> Self {
+        PortSevenSegment {
+            common: Default::default(),
+            polarity,
+            port,
+            value: None,
+            segments: Segments::default(),
+            dp: false,
+        }
+    }
+
+    /// Returns the value that was last set, see `SevenSegment::current`.
+    pub fn current(&self) -> Option<u8> {
+        self.value
+    }
+
+    /// Returns the segments that were last set.
+    pub fn segments(&self) -> Segments {
+        self.segments
+    }
+
+    /// Deconstructs the display and returns the port.
+    pub fn into_port(self) -> W {
+        self.port
+    }
+}
+
+impl<W: PortWriter, Common: Polarity> PortSevenSegment<W, Common> {
+    /// Sets the value of the display.
+    ///
+    /// The valid values are 0-15, values 10-15 are shown as hexadecimal digits. In case of
+    /// invalid value, the display will be blank.
+    ///
+    /// # Panics
+    ///
+    /// If the `strict` feature is enabled invalid values panic instead, just like in
+    /// `SevenSegment::set`.
+    pub fn set(&mut self, value: u8) -> Result<(), W::Error> {
+        super::check_value(value, 0xf);
+        self.set_segments(Segments::from_hex_digit(value).unwrap_or_default())?;
+        if value <= 0xf {
+            self.value = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Sets the value of the display if it's a valid digit.
+    ///
+    /// Values above 9 return an error and leave the display unchanged.
+    pub fn try_set(&mut self, value: u8) -> Result<(), SetError<InvalidDigit, W::Error>> {
+        if value > 9 {
+            return Err(SetError::Invalid(InvalidDigit(value)));
+        }
+        self.set(value).map_err(SetError::Pin)
+    }
+
+    /// Lights exactly the given segments.
+    ///
+    /// The decimal point is not affected.
+    pub fn set_segments(&mut self, segments: Segments) -> Result<(), W::Error> {
+        self.write(segments, self.dp)?;
+        self.value = None;
+        self.segments = segments;
+        Ok(())
+    }
+
+    /// Lights or turns off the decimal point.
+    pub fn set_dp(&mut self, on: bool) -> Result<(), W::Error> {
+        self.write(self.segments, on)?;
+        self.dp = on;
+        Ok(())
+    }
+
+    /// Turns off all segments.
+    ///
+    /// The decimal point is not affected.
+    pub fn blank(&mut self) -> Result<(), W::Error> {
+        self.set_segments(Segments::default())
+    }
+
+    fn write(&mut self, segments: Segments, dp: bool) -> Result<(), W::Error> {
+        let lit = segments.bits() | (dp as u8) << 7;
+        let levels = if Common::is_cathode(self.polarity) {
+            lit
+        } else {
+            !lit
+        };
+        self.port.write(levels)
+    }
+}
+
+impl<W: PortWriter, Common: Polarity> SegmentSink for PortSevenSegment<W, Common> {
+    type Error = W::Error;
+
+    fn set_digit(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.set(value)
+    }
+
+    fn set_segments(&mut self, segments: Segments) -> Result<(), Self::Error> {
+        PortSevenSegment::set_segments(self, segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PortSevenSegment, PortWriter};
+    use crate::{Anode, Cathode, Segments};
+
+    #[derive(Default)]
+    struct TestPort {
+        levels: Option<u8>,
+        writes: usize,
+    }
+
+    impl PortWriter for TestPort {
+        type Error = core::convert::Infallible;
+
+        fn write(&mut self, levels: u8) -> Result<(), Self::Error> {
+            self.levels = Some(levels);
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct TestSpi {
+        sent: Option<u8>,
+    }
+
+    impl embedded_hal::blocking::spi::Write<u8> for TestSpi {
+        type Error = core::convert::Infallible;
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            self.sent = Some(words[0]);
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "strict", should_panic)]
+    fn invalid() {
+        let mut port = TestPort::default();
+        let mut display = PortSevenSegment::<_, Cathode>::new(&mut port);
+        display.set(16).unwrap();
+        assert_eq!(display.current(), None);
+        assert_eq!(port.levels, Some(0));
+    }
+
+    #[test]
+    fn shift_register() {
+        use super::{ShiftRegister, ShiftRegisterSevenSegment};
+        use crate::mock::MockPin;
+
+        let register = ShiftRegister::new(TestSpi::default(), MockPin::default());
+        let mut display = ShiftRegisterSevenSegment::<_, _, Cathode>::new(register);
+        display.set(2).unwrap();
+        let (spi, latch) = display.into_port().into_parts();
+        assert_eq!(spi.sent, Some(0b0101_1011));
+        assert_eq!(latch.is_high(), Some(true));
+    }
+
+    #[test]
+    fn batched() {
+        let mut port = TestPort::default();
+        let mut display = PortSevenSegment::<_, Cathode>::new(&mut port);
+        display.set(7).unwrap();
+        display.set_dp(true).unwrap();
+        assert_eq!(display.current(), Some(7));
+        display.set_segments(Segments::MINUS).unwrap();
+        assert_eq!(display.current(), None);
+        assert_eq!(port.levels, Some(0b1100_0000));
+        assert_eq!(port.writes, 3);
+
+        let mut port = TestPort::default();
+        let mut display = PortSevenSegment::<_, Anode>::new(&mut port);
+        display.set(1).unwrap();
+        assert_eq!(port.levels, Some(!0b0000_0110));
+        assert_eq!(port.writes, 1);
+    }
+}
@@ -0,0 +1,142 @@
+//! Skipping writes of pins that are already at the right level
+//!
+//! If the pins implement `StatefulOutputPin` the `*_changed` methods read the level the pins are
+//! set to and only write those that need to change. This reduces traffic for pins behind a bus,
+//! e.g. an I2C expander. Pins implementing only `OutputPin` can be used with the normal methods,
+//! which write all pins unconditionally.
+
+use super::{OptionalPin, PinError, Polarity, Segment, SegmentObserver, Segments, SevenSegment};
+use embedded_hal::digital::v2::StatefulOutputPin;
+
+/// Drives the pin to the level that makes the segment lit or unlit unless it's already there.
+fn write_segment<P: StatefulOutputPin>(
+    pin: &mut P,
+    on: bool,
+    cathode: bool,
+) -> Result<(), P::Error> {
+    let high = on == cathode;
+    if pin.is_set_high()? != high {
+        super::write_segment(pin, on, cathode)?;
+    }
+    Ok(())
+}
+
+impl<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+    SevenSegment<A, B, C, D, E, F, G, Common, DP, CommonPin, Observer, Aux>
+where
+    A: StatefulOutputPin,
+    B: StatefulOutputPin<Error = A::Error>,
+    C: StatefulOutputPin<Error = A::Error>,
+    D: StatefulOutputPin<Error = A::Error>,
+    E: StatefulOutputPin<Error = A::Error>,
+    F: StatefulOutputPin<Error = A::Error>,
+    G: StatefulOutputPin<Error = A::Error>,
+    Common: Polarity,
+    DP: OptionalPin<A::Error>,
+    CommonPin: OptionalPin<A::Error>,
+    Observer: SegmentObserver,
+{
+    /// Sets the value of the display writing only the pins that change.
+    ///
+    /// This behaves the same as `set`.
+    pub fn set_changed(&mut self, value: u8) -> Result<(), PinError<A::Error>> {
+        super::check_value(value, self.max_value());
+        self.set_segments_changed(self.glyph(value))?;
+        if value <= self.max_value() {
+            self.state.value = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Lights exactly the given segments writing only the pins that change.
+    ///
+    /// This behaves the same as `set_segments`. The common pin, if any, is still written.
+    pub fn set_segments_changed(&mut self, segments: Segments) -> Result<(), PinError<A::Error>> {
+        self.state.value = None;
+        self.state.segments = segments;
+        let segments = match self.begin_write(segments) {
+            Some(segments) => segments,
+            None => return Ok(()),
+        };
+        let cathode = Common::is_cathode(self.state.polarity);
+        let inverted = self.state.inverted;
+        self.disable_if_blank(segments)?;
+        write_segment(&mut self.a, segments.a, cathode != inverted.a)
+            .map_err(PinError::at(Segment::A))?;
+        write_segment(&mut self.b, segments.b, cathode != inverted.b)
+            .map_err(PinError::at(Segment::B))?;
+        write_segment(&mut self.c, segments.c, cathode != inverted.c)
+            .map_err(PinError::at(Segment::C))?;
+        write_segment(&mut self.d, segments.d, cathode != inverted.d)
+            .map_err(PinError::at(Segment::D))?;
+        write_segment(&mut self.e, segments.e, cathode != inverted.e)
+            .map_err(PinError::at(Segment::E))?;
+        write_segment(&mut self.f, segments.f, cathode != inverted.f)
+            .map_err(PinError::at(Segment::F))?;
+        write_segment(&mut self.g, segments.g, cathode != inverted.g)
+            .map_err(PinError::at(Segment::G))?;
+        self.enable_if_lit(segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OutputPin, SevenSegmentPins};
+    use embedded_hal::digital::v2::StatefulOutputPin;
+
+    #[derive(Default)]
+    struct CountingPin {
+        high: bool,
+        writes: u32,
+    }
+
+    impl OutputPin for &'_ mut CountingPin {
+        type Error = core::convert::Infallible;
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.high = true;
+            self.writes += 1;
+            Ok(())
+        }
+
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.high = false;
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    impl StatefulOutputPin for &'_ mut CountingPin {
+        fn is_set_high(&self) -> Result<bool, Self::Error> {
+            Ok(self.high)
+        }
+
+        fn is_set_low(&self) -> Result<bool, Self::Error> {
+            Ok(!self.high)
+        }
+    }
+
+    #[test]
+    fn skips_unchanged() {
+        let mut pins: [CountingPin; 7] = Default::default();
+        {
+            let [a, b, c, d, e, f, g] = &mut pins;
+            let mut display = SevenSegmentPins {
+                a,
+                b,
+                c,
+                d,
+                e,
+                f,
+                g,
+            }
+            .with_common_cathode();
+            display.set_changed(8).unwrap();
+            display.set_changed(0).unwrap();
+            assert_eq!(display.current(), Some(0));
+        }
+        let writes: [u32; 7] = core::array::from_fn(|i| pins[i].writes);
+        assert_eq!(writes, [1, 1, 1, 1, 1, 1, 2]);
+        assert!(!pins[6].high);
+    }
+}